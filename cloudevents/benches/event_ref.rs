@@ -0,0 +1,79 @@
+//! Compares deserializing an event into the owned `CloudEventV1_0` and the borrowed
+//! `CloudEventRef`, reporting the allocations of each before measuring their time.
+
+use cloudevents::v1_0::{CloudEventRef, CloudEventV1_0};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counts the allocations of the benchmark.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const EVENT: &str = r#"{
+    "specversion": "1.0",
+    "type": "com.example.order.created",
+    "source": "https://example.com/orders",
+    "id": "5c6c1c2e-2b8b-4d2a-9a4e-0c3d1f0b4f1e",
+    "time": "2020-01-01T00:00:00Z",
+    "subject": "orders/42",
+    "dataschema": "https://example.com/schemas/order.json",
+    "datacontenttype": "application/json",
+    "traceparent": "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+    "partitionkey": "customer-7",
+    "data": {
+        "id": 42,
+        "customer": "customer-7",
+        "items": [
+            {"sku": "book", "quantity": 1, "price": 12.5},
+            {"sku": "pen", "quantity": 3, "price": 1.2}
+        ]
+    }
+}"#;
+
+/// Number of allocations made by `f`.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let value = f();
+    let after = ALLOCATIONS.load(Ordering::Relaxed);
+    drop(value);
+    after - before
+}
+
+fn deserialize(c: &mut Criterion) {
+    println!(
+        "allocations per event: CloudEventV1_0 {}, CloudEventRef {}",
+        allocations(|| serde_json::from_str::<CloudEventV1_0>(EVENT).unwrap()),
+        allocations(|| serde_json::from_str::<CloudEventRef>(EVENT).unwrap()),
+    );
+
+    let mut group = c.benchmark_group("deserialize");
+    group.bench_function("CloudEventV1_0", |b| {
+        b.iter(|| serde_json::from_str::<CloudEventV1_0>(black_box(EVENT)).unwrap())
+    });
+    group.bench_function("CloudEventRef", |b| {
+        b.iter(|| serde_json::from_str::<CloudEventRef>(black_box(EVENT)).unwrap())
+    });
+    group.bench_function("CloudEventRef from bytes", |b| {
+        b.iter(|| serde_json::from_slice::<CloudEventRef>(black_box(EVENT.as_bytes())).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
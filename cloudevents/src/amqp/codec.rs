@@ -0,0 +1,300 @@
+//! Encoding of the message sections with the AMQP 1.0 type system.
+
+use super::{AmqpMessage, AmqpValue};
+use crate::Error;
+use std::convert::TryFrom;
+
+/// Descriptors of the message sections.
+const HEADER: u8 = 0x70;
+const DELIVERY_ANNOTATIONS: u8 = 0x71;
+const MESSAGE_ANNOTATIONS: u8 = 0x72;
+const PROPERTIES: u8 = 0x73;
+const APPLICATION_PROPERTIES: u8 = 0x74;
+const DATA: u8 = 0x75;
+const FOOTER: u8 = 0x78;
+
+/// Index of the `content-type` field in the properties list.
+const CONTENT_TYPE_FIELD: usize = 6;
+
+pub(crate) fn encode(message: &AmqpMessage) -> Vec<u8> {
+    let mut out = Vec::new();
+    if let Some(content_type) = &message.content_type {
+        let mut fields = vec![0x40; CONTENT_TYPE_FIELD];
+        write_variable(&mut fields, 0xa3, content_type.as_bytes());
+        write_section(&mut out, PROPERTIES);
+        write_compound(&mut out, 0xc0, CONTENT_TYPE_FIELD + 1, &fields);
+    }
+    if !message.application_properties.is_empty() {
+        let mut entries = Vec::new();
+        for (key, value) in &message.application_properties {
+            write_variable(&mut entries, 0xa1, key.as_bytes());
+            write_value(&mut entries, value);
+        }
+        write_section(&mut out, APPLICATION_PROPERTIES);
+        write_compound(
+            &mut out,
+            0xc1,
+            message.application_properties.len() * 2,
+            &entries,
+        );
+    }
+    if let Some(body) = &message.body {
+        write_section(&mut out, DATA);
+        write_variable(&mut out, 0xa0, body);
+    }
+    out
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<AmqpMessage, Error> {
+    let mut decoder = Decoder(bytes);
+    let mut message = AmqpMessage::default();
+    while !decoder.0.is_empty() {
+        if decoder.u8()? != 0x00 {
+            return Err(malformed("expected a message section"));
+        }
+        let descriptor = match decoder.u8()? {
+            0x53 => decoder.u8()?,
+            0x80 => match u8::try_from(decoder.u64()?) {
+                Ok(descriptor) => descriptor,
+                Err(_) => return Err(malformed("unknown message section")),
+            },
+            _ => return Err(malformed("unsupported section descriptor")),
+        };
+        match descriptor {
+            PROPERTIES => message.content_type = decoder.content_type()?,
+            APPLICATION_PROPERTIES => {
+                message.application_properties = decoder.application_properties()?
+            }
+            DATA => {
+                let constructor = decoder.u8()?;
+                let data = decoder.variable(constructor)?;
+                message
+                    .body
+                    .get_or_insert_with(Vec::new)
+                    .extend_from_slice(data);
+            }
+            HEADER | DELIVERY_ANNOTATIONS | MESSAGE_ANNOTATIONS | FOOTER => {
+                let constructor = decoder.u8()?;
+                decoder.skip(constructor)?;
+            }
+            _ => return Err(malformed("only data sections are supported as body")),
+        }
+    }
+    Ok(message)
+}
+
+fn malformed(reason: &'static str) -> Error {
+    Error::InvalidAmqpMessage(reason)
+}
+
+fn write_section(out: &mut Vec<u8>, descriptor: u8) {
+    out.extend_from_slice(&[0x00, 0x53, descriptor]);
+}
+
+/// Write a binary, string or symbol, using the 4 bytes size constructor when needed.
+fn write_variable(out: &mut Vec<u8>, constructor: u8, bytes: &[u8]) {
+    match u8::try_from(bytes.len()) {
+        Ok(size) => out.extend_from_slice(&[constructor, size]),
+        Err(_) => {
+            out.push(constructor + 0x10);
+            out.extend_from_slice(&(bytes.len() as u32).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(bytes);
+}
+
+/// Write a list or map, using the 4 bytes size and count constructor when needed.
+fn write_compound(out: &mut Vec<u8>, constructor: u8, count: usize, items: &[u8]) {
+    match (u8::try_from(items.len() + 1), u8::try_from(count)) {
+        (Ok(size), Ok(count)) => out.extend_from_slice(&[constructor, size, count]),
+        _ => {
+            out.push(constructor + 0x10);
+            out.extend_from_slice(&(items.len() as u32 + 4).to_be_bytes());
+            out.extend_from_slice(&(count as u32).to_be_bytes());
+        }
+    }
+    out.extend_from_slice(items);
+}
+
+fn write_value(out: &mut Vec<u8>, value: &AmqpValue) {
+    match value {
+        AmqpValue::Null => out.push(0x40),
+        AmqpValue::Boolean(true) => out.push(0x41),
+        AmqpValue::Boolean(false) => out.push(0x42),
+        AmqpValue::Long(l) => {
+            out.push(0x81);
+            out.extend_from_slice(&l.to_be_bytes());
+        }
+        AmqpValue::Double(d) => {
+            out.push(0x82);
+            out.extend_from_slice(&d.to_bits().to_be_bytes());
+        }
+        AmqpValue::Timestamp(t) => {
+            out.push(0x83);
+            out.extend_from_slice(&t.to_be_bytes());
+        }
+        AmqpValue::Uuid(u) => {
+            out.push(0x98);
+            out.extend_from_slice(u);
+        }
+        AmqpValue::Binary(b) => write_variable(out, 0xa0, b),
+        AmqpValue::String(s) => write_variable(out, 0xa1, s.as_bytes()),
+        AmqpValue::Symbol(s) => write_variable(out, 0xa3, s.as_bytes()),
+    }
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(malformed("unexpected end of message"));
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        let mut buf = [0; 2];
+        buf.copy_from_slice(self.take(2)?);
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        let mut buf = [0; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    /// Read the size of a variable width or compound value.
+    fn size(&mut self, constructor: u8) -> Result<usize, Error> {
+        match constructor >> 4 {
+            0xa | 0xc | 0xe => Ok(usize::from(self.u8()?)),
+            0xb | 0xd | 0xf => Ok(self.u32()? as usize),
+            _ => Err(malformed("expected a variable width value")),
+        }
+    }
+
+    fn variable(&mut self, constructor: u8) -> Result<&'a [u8], Error> {
+        match constructor {
+            0xa0 | 0xb0 | 0xa1 | 0xb1 | 0xa3 | 0xb3 => {
+                let size = self.size(constructor)?;
+                self.take(size)
+            }
+            _ => Err(malformed("expected a binary, string or symbol")),
+        }
+    }
+
+    fn string(&mut self, constructor: u8) -> Result<String, Error> {
+        String::from_utf8(self.variable(constructor)?.to_vec())
+            .map_err(|_| malformed("invalid UTF-8 string"))
+    }
+
+    /// Skip a value of any type, relying on the width encoded in its constructor.
+    fn skip(&mut self, constructor: u8) -> Result<(), Error> {
+        let width = match constructor >> 4 {
+            0x0 if constructor == 0x00 => {
+                let descriptor = self.u8()?;
+                self.skip(descriptor)?;
+                let constructor = self.u8()?;
+                return self.skip(constructor);
+            }
+            0x4 => 0,
+            0x5 => 1,
+            0x6 => 2,
+            0x7 => 4,
+            0x8 => 8,
+            0x9 => 16,
+            0xa..=0xf => self.size(constructor)?,
+            _ => return Err(malformed("invalid constructor")),
+        };
+        self.take(width).map(|_| ())
+    }
+
+    /// Read the list header of a compound value, returning the number of items.
+    fn list(&mut self, constructor: u8) -> Result<usize, Error> {
+        match constructor {
+            0x45 => Ok(0),
+            0xc0 | 0xc1 => {
+                self.u8()?;
+                Ok(usize::from(self.u8()?))
+            }
+            0xd0 | 0xd1 => {
+                self.u32()?;
+                Ok(self.u32()? as usize)
+            }
+            _ => Err(malformed("expected a list or map")),
+        }
+    }
+
+    fn content_type(&mut self) -> Result<Option<String>, Error> {
+        let constructor = self.u8()?;
+        let count = self.list(constructor)?;
+        let mut content_type = None;
+        for field in 0..count {
+            let constructor = self.u8()?;
+            if field == CONTENT_TYPE_FIELD && constructor != 0x40 {
+                content_type = Some(self.string(constructor)?);
+            } else {
+                self.skip(constructor)?;
+            }
+        }
+        Ok(content_type)
+    }
+
+    fn application_properties(&mut self) -> Result<Vec<(String, AmqpValue)>, Error> {
+        let constructor = self.u8()?;
+        let count = self.list(constructor)?;
+        let mut properties = Vec::with_capacity(count / 2);
+        for _ in 0..count / 2 {
+            let constructor = self.u8()?;
+            let key = self.string(constructor)?;
+            let constructor = self.u8()?;
+            properties.push((key, self.value(constructor)?));
+        }
+        Ok(properties)
+    }
+
+    fn value(&mut self, constructor: u8) -> Result<AmqpValue, Error> {
+        Ok(match constructor {
+            0x40 => AmqpValue::Null,
+            0x41 => AmqpValue::Boolean(true),
+            0x42 => AmqpValue::Boolean(false),
+            0x56 => AmqpValue::Boolean(self.u8()? != 0),
+            0x43 | 0x44 => AmqpValue::Long(0),
+            0x50 | 0x52 | 0x53 => AmqpValue::Long(i64::from(self.u8()?)),
+            0x51 | 0x54 | 0x55 => AmqpValue::Long(i64::from(self.u8()? as i8)),
+            0x60 => AmqpValue::Long(i64::from(self.u16()?)),
+            0x61 => AmqpValue::Long(i64::from(self.u16()? as i16)),
+            0x70 => AmqpValue::Long(i64::from(self.u32()?)),
+            0x71 => AmqpValue::Long(i64::from(self.u32()? as i32)),
+            0x72 => AmqpValue::Double(f64::from(f32::from_bits(self.u32()?))),
+            0x80 => AmqpValue::Long(
+                i64::try_from(self.u64()?).map_err(|_| malformed("ulong out of range"))?,
+            ),
+            0x81 => AmqpValue::Long(self.u64()? as i64),
+            0x82 => AmqpValue::Double(f64::from_bits(self.u64()?)),
+            0x83 => AmqpValue::Timestamp(self.u64()? as i64),
+            0x98 => {
+                let mut uuid = [0; 16];
+                uuid.copy_from_slice(self.take(16)?);
+                AmqpValue::Uuid(uuid)
+            }
+            0xa0 | 0xb0 => AmqpValue::Binary(self.variable(constructor)?.to_vec()),
+            0xa1 | 0xb1 => AmqpValue::String(self.string(constructor)?),
+            0xa3 | 0xb3 => AmqpValue::Symbol(self.string(constructor)?),
+            _ => return Err(malformed("unsupported application property type")),
+        })
+    }
+}
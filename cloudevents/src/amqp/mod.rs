@@ -0,0 +1,349 @@
+/*!
+AMQP 1.0 protocol binding.
+
+Implements the AMQP protocol binding (see [v1.0 AMQP Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/amqp-protocol-binding.md))
+on top of [`AmqpMessage`], a client independent model of an AMQP 1.0 message, in two content modes:
+
+* **Binary**: the context attributes and extensions are mapped to `cloudEvents:` prefixed application properties,
+  the content type attribute is mapped to the `content-type` message property and the body carries the raw data.
+* **Structured**: the body carries the event in the JSON format, with the `application/cloudevents+json`
+  content type.
+
+When reading a message, the content mode is detected from its `content-type` property.
+
+Application properties keep the type of the attributes and extensions: `time` and timestamp extensions
+are AMQP timestamps, booleans, integers and binary extensions are mapped to the AMQP types of the same
+name, and everything else to strings. AMQP timestamps have a millisecond precision, so a finer `time`
+is truncated to the millisecond in binary mode, while structured mode keeps it as is.
+
+# Example
+
+```
+use cloudevents::amqp::{from_message, to_binary_message, AmqpMessage, AmqpValue};
+use cloudevents::cloudevent;
+use cloudevents::{CloudEvent, Data};
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    datacontenttype: "text/plain",
+    data: Data::from_string("hello world"),
+).unwrap().into();
+
+let message = to_binary_message(&event).unwrap();
+assert_eq!(message.property("cloudEvents:id"), Some(&AmqpValue::String("id".to_owned())));
+assert_eq!(message.content_type.as_deref(), Some("text/plain"));
+
+let bytes = message.to_bytes();
+let decoded = from_message(&AmqpMessage::from_bytes(&bytes).unwrap()).unwrap();
+assert_eq!(decoded, event);
+```
+
+[`AmqpMessage`]: struct.AmqpMessage.html
+*/
+mod codec;
+
+use crate::message::{
+    data_from_bytes, data_to_bytes, ensure_json_format, format_time, ContentMode,
+    EventDeserializer, EventSerializer, CLOUDEVENTS_BATCH_JSON, CLOUDEVENTS_JSON,
+};
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use chrono::prelude::{DateTime, FixedOffset, TimeZone, Utc};
+
+/// Prefix of the application properties carrying context attributes and extensions.
+const PREFIX: &str = "cloudEvents:";
+
+/// Prefix used by later revisions of the binding, accepted when reading.
+const ALTERNATIVE_PREFIX: &str = "cloudEvents_";
+
+/// An AMQP 1.0 message, reduced to the sections used by the protocol binding.
+///
+/// Use [`to_bytes`] and [`from_bytes`] to convert it from and to the AMQP 1.0 encoding
+/// of the message sections. When decoding, the header, annotations and footer sections
+/// are skipped, and the body must consist of data sections.
+///
+/// [`to_bytes`]: #method.to_bytes
+/// [`from_bytes`]: #method.from_bytes
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct AmqpMessage {
+    /// The `content-type` field of the properties section.
+    pub content_type: Option<String>,
+    /// The application properties, in order.
+    pub application_properties: Vec<(String, AmqpValue)>,
+    /// The content of the data sections.
+    pub body: Option<Vec<u8>>,
+}
+
+/// Values of application properties, according to the AMQP 1.0 type system
+///
+/// All integer types are read as [`Long`].
+///
+/// [`Long`]: #variant.Long
+#[derive(Debug, PartialEq, Clone)]
+pub enum AmqpValue {
+    /// The `null` value.
+    Null,
+    /// A `boolean` value.
+    Boolean(bool),
+    /// A `long` value.
+    Long(i64),
+    /// A `double` value.
+    Double(f64),
+    /// A `timestamp` value, in milliseconds since the Unix epoch.
+    Timestamp(i64),
+    /// A `uuid` value.
+    Uuid([u8; 16]),
+    /// A `binary` value.
+    Binary(Vec<u8>),
+    /// A `string` value.
+    String(String),
+    /// A `symbol` value.
+    Symbol(String),
+}
+
+impl From<bool> for AmqpValue {
+    fn from(b: bool) -> Self {
+        AmqpValue::Boolean(b)
+    }
+}
+
+impl From<i64> for AmqpValue {
+    fn from(l: i64) -> Self {
+        AmqpValue::Long(l)
+    }
+}
+
+impl From<&str> for AmqpValue {
+    fn from(s: &str) -> Self {
+        AmqpValue::String(s.to_owned())
+    }
+}
+
+impl From<String> for AmqpValue {
+    fn from(s: String) -> Self {
+        AmqpValue::String(s)
+    }
+}
+
+impl From<Vec<u8>> for AmqpValue {
+    fn from(b: Vec<u8>) -> Self {
+        AmqpValue::Binary(b)
+    }
+}
+
+impl AmqpMessage {
+    /// Get the value of an application property.
+    pub fn property(&self, name: &str) -> Option<&AmqpValue> {
+        self.application_properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Encode the message sections in the AMQP 1.0 format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    /// Decode the message sections from the AMQP 1.0 format.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the input is not a valid sequence of message sections,
+    /// or if the body is not made of data sections.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        codec::decode(bytes)
+    }
+}
+
+/// Create a binary mode message carrying the event.
+///
+/// The `time` attribute and timestamp extensions are truncated to the millisecond,
+/// the precision of AMQP timestamps.
+///
+/// # Errors
+///
+/// An error is returned if the data of the event cannot be serialized.
+pub fn to_binary_message(event: &CloudEvent) -> Result<AmqpMessage, Error> {
+    event.clone().deserialize_event(BinaryWriter {
+        message: AmqpMessage::default(),
+        content_type: None,
+    })
+}
+
+/// Create a structured mode message carrying the event in the JSON format.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_structured_message(event: &CloudEvent) -> Result<AmqpMessage, Error> {
+    Ok(AmqpMessage {
+        content_type: Some(CLOUDEVENTS_JSON.to_owned()),
+        application_properties: Vec::new(),
+        body: Some(serde_json::to_vec(event)?),
+    })
+}
+
+/// Read the event carried by a binary or structured mode message.
+///
+/// # Errors
+///
+/// An error is returned if the message is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_message(message: &AmqpMessage) -> Result<CloudEvent, Error> {
+    let content_type = message.content_type.as_deref();
+    match ContentMode::from_content_type(content_type) {
+        ContentMode::Binary => BinaryReader(message).into_event(),
+        ContentMode::Structured => {
+            ensure_json_format(content_type, CLOUDEVENTS_JSON)?;
+            Ok(serde_json::from_slice(
+                message.body.as_deref().unwrap_or_default(),
+            )?)
+        }
+        ContentMode::Batch => Err(Error::UnsupportedContentType(
+            CLOUDEVENTS_BATCH_JSON.to_owned(),
+        )),
+    }
+}
+
+/// Writes the attributes and extensions to application properties, and the data to the body.
+struct BinaryWriter {
+    message: AmqpMessage,
+    content_type: Option<&'static str>,
+}
+
+impl BinaryWriter {
+    fn push(&mut self, name: &str, value: AmqpValue) {
+        self.message
+            .application_properties
+            .push((format!("{}{}", PREFIX, name), value));
+    }
+}
+
+impl EventSerializer<AmqpMessage> for BinaryWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.content_type = Some(version.content_type_attribute());
+        self.push(
+            "specversion",
+            AmqpValue::String(version.as_str().to_owned()),
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        if self.content_type == Some(name) {
+            self.message.content_type = Some(value);
+        } else if name == "time" {
+            let time = DateTime::parse_from_rfc3339(&value)
+                .map_err(|source| Error::InvalidTime { source })?;
+            self.push(name, AmqpValue::Timestamp(time.timestamp_millis()));
+        } else {
+            self.push(name, AmqpValue::String(value));
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let value = match value {
+            ExtensionValue::Boolean(b) => AmqpValue::Boolean(b),
+            ExtensionValue::Integer(i) => AmqpValue::Long(i),
+            ExtensionValue::Binary(b) => AmqpValue::Binary(b),
+            ExtensionValue::Timestamp(t) => AmqpValue::Timestamp(t.timestamp_millis()),
+            v => AmqpValue::String(v.to_string()),
+        };
+        self.push(name, value);
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<AmqpMessage, Error> {
+        self.message.body = Some(data_to_bytes(data)?);
+        Ok(self.message)
+    }
+
+    fn end(self) -> Result<AmqpMessage, Error> {
+        Ok(self.message)
+    }
+}
+
+/// Reads the attributes and extensions from application properties, and the data from the body.
+struct BinaryReader<'a>(&'a AmqpMessage);
+
+impl<'a> EventDeserializer for BinaryReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let message = self.0;
+        let properties = message
+            .application_properties
+            .iter()
+            .filter_map(|(name, value)| {
+                let attribute = name
+                    .strip_prefix(PREFIX)
+                    .or_else(|| name.strip_prefix(ALTERNATIVE_PREFIX))?;
+                Some((attribute, value))
+            });
+
+        let spec_version: SpecVersion = match properties
+            .clone()
+            .find(|(attribute, _)| *attribute == "specversion")
+        {
+            Some((_, AmqpValue::String(value))) => value.parse()?,
+            Some(_) => return Err(Error::InvalidAttributeValue("specversion".to_owned())),
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let attributes = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        for (attribute, value) in properties {
+            let invalid = || Error::InvalidAttributeValue(attribute.to_owned());
+            serializer = match attribute {
+                "specversion" => serializer,
+                a if attributes.contains(&a) => {
+                    let value = match value {
+                        AmqpValue::String(s) | AmqpValue::Symbol(s) => s.clone(),
+                        AmqpValue::Timestamp(t) => format_time(&timestamp(*t).ok_or_else(invalid)?),
+                        _ => return Err(invalid()),
+                    };
+                    serializer.set_attribute(a, value)?
+                }
+                a => {
+                    let value = match value {
+                        AmqpValue::Boolean(b) => ExtensionValue::Boolean(*b),
+                        AmqpValue::Long(l) => ExtensionValue::Integer(*l),
+                        AmqpValue::Binary(b) => ExtensionValue::Binary(b.clone()),
+                        AmqpValue::String(s) | AmqpValue::Symbol(s) => {
+                            ExtensionValue::String(s.clone())
+                        }
+                        AmqpValue::Timestamp(t) => {
+                            ExtensionValue::Timestamp(timestamp(*t).ok_or_else(invalid)?)
+                        }
+                        _ => return Err(invalid()),
+                    };
+                    serializer.set_extension(a, value)?
+                }
+            };
+        }
+
+        let content_type = message.content_type.as_deref();
+        if let Some(content_type) = content_type {
+            serializer = serializer.set_attribute(
+                spec_version.content_type_attribute(),
+                content_type.to_owned(),
+            )?;
+        }
+
+        match &message.body {
+            Some(body) if !body.is_empty() => {
+                serializer.end_with_data(data_from_bytes(content_type, body.clone())?)
+            }
+            _ => serializer.end(),
+        }
+    }
+}
+
+/// Convert an AMQP timestamp, in milliseconds since the Unix epoch.
+fn timestamp(millis: i64) -> Option<DateTime<FixedOffset>> {
+    let nanos = (millis.rem_euclid(1000) * 1_000_000) as u32;
+    Utc.timestamp_opt(millis.div_euclid(1000), nanos)
+        .single()
+        .map(DateTime::from)
+}
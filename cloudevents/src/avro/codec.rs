@@ -0,0 +1,341 @@
+//! Binary encoding of the `CloudEvent` record according to the Avro specification.
+
+use super::{number, AvroAttribute, AvroData, AvroRecord};
+use crate::Error;
+use serde_json::{Map, Value};
+use std::convert::TryFrom;
+
+/// Key of the single entry of the map of a `CloudEventData` record.
+const DATA_VALUE_KEY: &str = "value";
+
+/// Maximum nesting of `CloudEventData` records, like the JSON parser.
+const MAX_DEPTH: usize = 128;
+
+pub(crate) fn encode(record: &AvroRecord) -> Vec<u8> {
+    let mut out = Vec::new();
+    write_count(&mut out, record.attribute.len());
+    for (name, value) in &record.attribute {
+        write_bytes(&mut out, name.as_bytes());
+        match value {
+            AvroAttribute::Null => write_long(&mut out, 0),
+            AvroAttribute::Boolean(b) => {
+                write_long(&mut out, 1);
+                out.push(u8::from(*b));
+            }
+            AvroAttribute::Int(i) => {
+                write_long(&mut out, 2);
+                write_long(&mut out, i64::from(*i));
+            }
+            AvroAttribute::String(s) => {
+                write_long(&mut out, 3);
+                write_bytes(&mut out, s.as_bytes());
+            }
+            AvroAttribute::Bytes(b) => {
+                write_long(&mut out, 4);
+                write_bytes(&mut out, b);
+            }
+        }
+    }
+    write_end(&mut out);
+
+    match &record.data {
+        AvroData::Bytes(b) => {
+            write_long(&mut out, 0);
+            write_bytes(&mut out, b);
+        }
+        AvroData::Null => write_long(&mut out, 1),
+        AvroData::Boolean(b) => {
+            write_long(&mut out, 2);
+            out.push(u8::from(*b));
+        }
+        AvroData::Map(members) => {
+            write_long(&mut out, 3);
+            write_count(&mut out, members.len());
+            for (name, value) in members {
+                write_bytes(&mut out, name.as_bytes());
+                write_member(&mut out, value);
+            }
+            write_end(&mut out);
+        }
+        AvroData::Array(items) => {
+            write_long(&mut out, 4);
+            write_records(&mut out, items);
+        }
+        AvroData::Double(d) => {
+            write_long(&mut out, 5);
+            out.extend_from_slice(&d.to_le_bytes());
+        }
+        AvroData::String(s) => {
+            write_long(&mut out, 6);
+            write_bytes(&mut out, s.as_bytes());
+        }
+    }
+    out
+}
+
+pub(crate) fn decode(bytes: &[u8]) -> Result<AvroRecord, Error> {
+    let mut decoder = Decoder(bytes);
+    let mut attribute = Vec::new();
+    decoder.blocks(|decoder| {
+        let name = decoder.string()?;
+        let value = match decoder.long()? {
+            0 => AvroAttribute::Null,
+            1 => AvroAttribute::Boolean(decoder.boolean()?),
+            2 => AvroAttribute::Int(
+                i32::try_from(decoder.long()?).map_err(|_| malformed("int out of range"))?,
+            ),
+            3 => AvroAttribute::String(decoder.string()?),
+            4 => AvroAttribute::Bytes(decoder.bytes()?.to_vec()),
+            _ => return Err(malformed("invalid branch of an attribute value")),
+        };
+        attribute.push((name, value));
+        Ok(())
+    })?;
+
+    let data = match decoder.long()? {
+        0 => AvroData::Bytes(decoder.bytes()?.to_vec()),
+        1 => AvroData::Null,
+        2 => AvroData::Boolean(decoder.boolean()?),
+        3 => {
+            let mut members = Map::new();
+            decoder.blocks(|decoder| {
+                let name = decoder.string()?;
+                let value = decoder.member()?;
+                members.insert(name, value);
+                Ok(())
+            })?;
+            AvroData::Map(members)
+        }
+        4 => AvroData::Array(decoder.records(0)?),
+        5 => AvroData::Double(decoder.double()?),
+        6 => AvroData::String(decoder.string()?),
+        _ => return Err(malformed("invalid branch of the data")),
+    };
+
+    if !decoder.0.is_empty() {
+        return Err(malformed("unexpected bytes after the record"));
+    }
+    Ok(AvroRecord { attribute, data })
+}
+
+fn malformed(reason: &'static str) -> Error {
+    Error::InvalidAvroRecord(reason)
+}
+
+/// Write a `long` with the variable length zig-zag encoding.
+fn write_long(out: &mut Vec<u8>, value: i64) {
+    let mut n = ((value << 1) ^ (value >> 63)) as u64;
+    while n >= 0x80 {
+        out.push((n as u8) | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+/// Write `bytes` or a `string`, prefixed with their length.
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_long(out, bytes.len() as i64);
+    out.extend_from_slice(bytes);
+}
+
+/// Start a map or array, written as a single block.
+fn write_count(out: &mut Vec<u8>, count: usize) {
+    if count > 0 {
+        write_long(out, count as i64);
+    }
+}
+
+/// End a map or array started with `write_count`.
+fn write_end(out: &mut Vec<u8>) {
+    write_long(out, 0);
+}
+
+/// Write a value of the map carried as data.
+fn write_member(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => write_long(out, 0),
+        Value::Bool(b) => {
+            write_long(out, 1);
+            out.push(u8::from(*b));
+        }
+        Value::Array(_) | Value::Object(_) => {
+            write_long(out, 2);
+            write_record(out, value);
+        }
+        Value::Number(n) => {
+            write_long(out, 3);
+            out.extend_from_slice(&n.as_f64().unwrap_or_default().to_le_bytes());
+        }
+        Value::String(s) => {
+            write_long(out, 4);
+            write_bytes(out, s.as_bytes());
+        }
+    }
+}
+
+/// Write an array of `CloudEventData` records.
+fn write_records(out: &mut Vec<u8>, items: &[Value]) {
+    write_count(out, items.len());
+    for item in items {
+        write_record(out, item);
+    }
+    write_end(out);
+}
+
+/// Write a `CloudEventData` record, holding the value under the `value` key of its map.
+fn write_record(out: &mut Vec<u8>, value: &Value) {
+    write_count(out, 1);
+    write_bytes(out, DATA_VALUE_KEY.as_bytes());
+    match value {
+        Value::Null => write_long(out, 0),
+        Value::Bool(b) => {
+            write_long(out, 1);
+            out.push(u8::from(*b));
+        }
+        Value::Object(members) => {
+            write_long(out, 2);
+            write_count(out, members.len());
+            for (name, value) in members {
+                write_bytes(out, name.as_bytes());
+                write_record(out, value);
+            }
+            write_end(out);
+        }
+        Value::Array(items) => {
+            write_long(out, 3);
+            write_records(out, items);
+        }
+        Value::Number(n) => {
+            write_long(out, 4);
+            out.extend_from_slice(&n.as_f64().unwrap_or_default().to_le_bytes());
+        }
+        Value::String(s) => {
+            write_long(out, 5);
+            write_bytes(out, s.as_bytes());
+        }
+    }
+    write_end(out);
+}
+
+struct Decoder<'a>(&'a [u8]);
+
+impl<'a> Decoder<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < n {
+            return Err(malformed("unexpected end of record"));
+        }
+        let (taken, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(taken)
+    }
+
+    fn long(&mut self) -> Result<i64, Error> {
+        let mut n = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.take(1)?[0];
+            n |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((n >> 1) as i64 ^ -((n & 1) as i64));
+            }
+        }
+        Err(malformed("long out of range"))
+    }
+
+    fn boolean(&mut self) -> Result<bool, Error> {
+        match self.take(1)?[0] {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(malformed("invalid boolean")),
+        }
+    }
+
+    fn double(&mut self) -> Result<f64, Error> {
+        let mut buf = [0; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(f64::from_le_bytes(buf))
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8], Error> {
+        let len = usize::try_from(self.long()?).map_err(|_| malformed("negative length"))?;
+        self.take(len)
+    }
+
+    fn string(&mut self) -> Result<String, Error> {
+        String::from_utf8(self.bytes()?.to_vec()).map_err(|_| malformed("invalid UTF-8 string"))
+    }
+
+    /// Read the blocks of a map or array, calling `item` for each of their items.
+    fn blocks(
+        &mut self,
+        mut item: impl FnMut(&mut Self) -> Result<(), Error>,
+    ) -> Result<(), Error> {
+        loop {
+            let count = self.long()?;
+            if count == 0 {
+                return Ok(());
+            }
+            if count < 0 {
+                // A negative count is followed by the size of the block in bytes.
+                self.long()?;
+            }
+            for _ in 0..count.unsigned_abs() {
+                item(self)?;
+            }
+        }
+    }
+
+    /// Read a value of the map carried as data.
+    fn member(&mut self) -> Result<Value, Error> {
+        Ok(match self.long()? {
+            0 => Value::Null,
+            1 => Value::Bool(self.boolean()?),
+            2 => self.record(0)?,
+            3 => number(self.double()?)?,
+            4 => Value::String(self.string()?),
+            _ => return Err(malformed("invalid branch of a data map value")),
+        })
+    }
+
+    /// Read an array of `CloudEventData` records.
+    fn records(&mut self, depth: usize) -> Result<Vec<Value>, Error> {
+        let mut items = Vec::new();
+        self.blocks(|decoder| {
+            items.push(decoder.record(depth)?);
+            Ok(())
+        })?;
+        Ok(items)
+    }
+
+    /// Read a `CloudEventData` record, which must hold a single entry under the `value` key.
+    fn record(&mut self, depth: usize) -> Result<Value, Error> {
+        if depth == MAX_DEPTH {
+            return Err(malformed("data nested too deeply"));
+        }
+        let mut value = None;
+        self.blocks(|decoder| {
+            if decoder.string()? != DATA_VALUE_KEY || value.is_some() {
+                return Err(malformed("expected a single value entry in CloudEventData"));
+            }
+            value = Some(match decoder.long()? {
+                0 => Value::Null,
+                1 => Value::Bool(decoder.boolean()?),
+                2 => {
+                    let mut members = Map::new();
+                    decoder.blocks(|decoder| {
+                        let name = decoder.string()?;
+                        let value = decoder.record(depth + 1)?;
+                        members.insert(name, value);
+                        Ok(())
+                    })?;
+                    Value::Object(members)
+                }
+                3 => Value::Array(decoder.records(depth + 1)?),
+                4 => number(decoder.double()?)?,
+                5 => Value::String(decoder.string()?),
+                _ => return Err(malformed("invalid branch of a CloudEventData value")),
+            });
+            Ok(())
+        })?;
+        value.ok_or_else(|| malformed("expected a single value entry in CloudEventData"))
+    }
+}
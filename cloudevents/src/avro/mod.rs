@@ -0,0 +1,298 @@
+/*!
+Avro event format.
+
+Implements the Avro event format (see [Avro Event Format for CloudEvents](https://github.com/cloudevents/spec/blob/main/cloudevents/formats/avro-format.md))
+on top of [`AvroRecord`], a model of the `io.cloudevents.CloudEvent` record defined by the canonical
+[`SCHEMA`], which is also shipped in `avro/cloudevents.avsc` for other languages. The format only
+supports spec version 1.0, use [`CloudEvent::to_version`] to convert events of other spec versions.
+
+The context attributes and extensions are mapped to the `attribute` map: booleans, integers and binary
+extensions keep their type, and everything else is mapped to strings. When reading a record, string
+extensions cannot be told apart from URI, URI-reference and timestamp extensions, and are read as strings.
+
+The data is mapped to the `data` union as follows:
+
+* Binary data is carried as `bytes`, and string data as `string`.
+* JSON data is carried with the type of its value, numbers being `double`. Nested objects and arrays
+  are carried in `CloudEventData` records, which hold the JSON value under the `value` key of their map.
+  When reading a `string`, data with a JSON content type is read as a JSON string.
+
+# Example
+
+```
+use cloudevents::avro::{from_bytes, to_bytes, AvroAttribute, AvroRecord};
+use cloudevents::v1_0::CloudEventV1_0Builder;
+use cloudevents::Data;
+use std::convert::TryFrom;
+
+let event = CloudEventV1_0Builder::default()
+    .event_type("test type")
+    .source("http://www.google.com")
+    .event_id("id")
+    .extension("count", 42)
+    .data(Data::from_binary(b"bytes"))
+    .build()
+    .unwrap();
+
+let record = AvroRecord::try_from(event.clone()).unwrap();
+assert_eq!(record.attribute("count"), Some(&AvroAttribute::Int(42)));
+
+let bytes = to_bytes(&event).unwrap();
+assert_eq!(from_bytes(&bytes).unwrap(), event);
+```
+
+[`AvroRecord`]: struct.AvroRecord.html
+[`SCHEMA`]: constant.SCHEMA.html
+[`CloudEvent::to_version`]: ../enum.CloudEvent.html#method.to_version
+*/
+mod codec;
+
+use crate::message::{
+    essence, expect_spec_version, format_time, is_json, EventDeserializer, EventSerializer,
+};
+use crate::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use crate::{Data, Error, ExtensionValue, SpecVersion};
+use serde_json::{Map, Number, Value};
+use std::convert::TryFrom;
+
+/// The canonical Avro schema of the format.
+pub const SCHEMA: &str = include_str!("../../avro/cloudevents.avsc");
+
+/// Content type of a structured mode message in the Avro format.
+pub const CLOUDEVENTS_AVRO: &str = "application/cloudevents+avro";
+
+/// Largest integer a double represents exactly.
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// A `CloudEvent` record of the Avro format.
+///
+/// Use [`to_bytes`] and [`from_bytes`] to convert it from and to the Avro binary encoding.
+///
+/// [`to_bytes`]: #method.to_bytes
+/// [`from_bytes`]: #method.from_bytes
+#[derive(Debug, PartialEq, Clone)]
+pub struct AvroRecord {
+    /// The `attribute` map, in order.
+    pub attribute: Vec<(String, AvroAttribute)>,
+    /// The `data` union.
+    pub data: AvroData,
+}
+
+/// Values of the `attribute` map.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AvroAttribute {
+    /// A `null` value.
+    Null,
+    /// A `boolean` value.
+    Boolean(bool),
+    /// An `int` value.
+    Int(i32),
+    /// A `string` value.
+    String(String),
+    /// A `bytes` value.
+    Bytes(Vec<u8>),
+}
+
+/// Branches of the `data` union.
+#[derive(Debug, PartialEq, Clone)]
+pub enum AvroData {
+    /// Binary data.
+    Bytes(Vec<u8>),
+    /// No data.
+    Null,
+    /// A JSON boolean.
+    Boolean(bool),
+    /// A JSON object.
+    Map(Map<String, Value>),
+    /// A JSON array.
+    Array(Vec<Value>),
+    /// A JSON number.
+    Double(f64),
+    /// String data, or a JSON string.
+    String(String),
+}
+
+impl AvroRecord {
+    /// Get the value of an entry of the `attribute` map.
+    pub fn attribute(&self, name: &str) -> Option<&AvroAttribute> {
+        self.attribute
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v)
+    }
+
+    /// Encode the record with the Avro binary encoding.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        codec::encode(self)
+    }
+
+    /// Decode the record from the Avro binary encoding.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the input is not a valid record of the schema.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        codec::decode(bytes)
+    }
+}
+
+/// Serialize the event in the Avro format.
+///
+/// # Errors
+///
+/// An error is returned if an extension cannot be represented in the format,
+/// e.g. an integer out of the 32 bits range.
+pub fn to_bytes(event: &CloudEventV1_0) -> Result<Vec<u8>, Error> {
+    Ok(AvroRecord::try_from(event.clone())?.to_bytes())
+}
+
+/// Parse an event in the Avro format.
+///
+/// # Errors
+///
+/// An error is returned if the input is not a valid record, or if it does not carry a valid event.
+pub fn from_bytes(bytes: &[u8]) -> Result<CloudEventV1_0, Error> {
+    CloudEventV1_0::try_from(AvroRecord::from_bytes(bytes)?)
+}
+
+impl TryFrom<CloudEventV1_0> for AvroRecord {
+    type Error = Error;
+
+    fn try_from(event: CloudEventV1_0) -> Result<Self, Error> {
+        event.deserialize_event(AvroWriter(Vec::new()))
+    }
+}
+
+impl TryFrom<AvroRecord> for CloudEventV1_0 {
+    type Error = Error;
+
+    fn try_from(record: AvroRecord) -> Result<Self, Error> {
+        record.deserialize_event(CloudEventV1_0Builder::default())
+    }
+}
+
+/// Writes an event to a `CloudEvent` record, collecting the `attribute` map.
+struct AvroWriter(Vec<(String, AvroAttribute)>);
+
+impl AvroWriter {
+    fn end_with(self, data: AvroData) -> AvroRecord {
+        AvroRecord {
+            attribute: self.0,
+            data,
+        }
+    }
+}
+
+impl EventSerializer<AvroRecord> for AvroWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V1_0, version)?;
+        self.0.push((
+            "specversion".to_owned(),
+            AvroAttribute::String(version.as_str().to_owned()),
+        ));
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        self.0.push((name.to_owned(), AvroAttribute::String(value)));
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let value = match value {
+            ExtensionValue::Boolean(b) => AvroAttribute::Boolean(b),
+            ExtensionValue::Integer(i) => AvroAttribute::Int(
+                i32::try_from(i).map_err(|_| Error::InvalidExtensionValue(name.to_owned()))?,
+            ),
+            ExtensionValue::String(s) | ExtensionValue::UriRef(s) => AvroAttribute::String(s),
+            ExtensionValue::Binary(b) => AvroAttribute::Bytes(b),
+            ExtensionValue::Uri(u) => AvroAttribute::String(u.as_str().to_owned()),
+            ExtensionValue::Timestamp(t) => AvroAttribute::String(format_time(&t)),
+            ExtensionValue::Object(_) => return Err(Error::InvalidExtensionValue(name.to_owned())),
+        };
+        self.0.push((name.to_owned(), value));
+        Ok(self)
+    }
+
+    fn end_with_data(self, data: Data) -> Result<AvroRecord, Error> {
+        let data = match data {
+            Data::Binary(bytes) => AvroData::Bytes(bytes),
+            Data::StringOrBinary(text) => AvroData::String(text),
+            Data::Object(Value::Null) => AvroData::Null,
+            Data::Object(Value::Bool(b)) => AvroData::Boolean(b),
+            Data::Object(Value::Number(n)) => AvroData::Double(n.as_f64().unwrap_or_default()),
+            Data::Object(Value::String(s)) => AvroData::String(s),
+            Data::Object(Value::Array(items)) => AvroData::Array(items),
+            Data::Object(Value::Object(members)) => AvroData::Map(members),
+        };
+        Ok(self.end_with(data))
+    }
+
+    fn end(self) -> Result<AvroRecord, Error> {
+        Ok(self.end_with(AvroData::Null))
+    }
+}
+
+impl EventDeserializer for AvroRecord {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let spec_version: SpecVersion = match self.attribute("specversion") {
+            Some(AvroAttribute::String(version)) => version.parse()?,
+            Some(_) => return Err(Error::InvalidAttributeValue("specversion".to_owned())),
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let names = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        let mut datacontenttype = None;
+        for (name, value) in self.attribute {
+            if name == "specversion" {
+                continue;
+            }
+            serializer = if names.contains(&name.as_str()) {
+                let value = match value {
+                    AvroAttribute::Null => continue,
+                    AvroAttribute::String(s) => s,
+                    _ => return Err(Error::InvalidAttributeValue(name)),
+                };
+                if name == spec_version.content_type_attribute() {
+                    datacontenttype = Some(value.clone());
+                }
+                serializer.set_attribute(&name, value)?
+            } else {
+                let value = match value {
+                    AvroAttribute::Null => continue,
+                    AvroAttribute::Boolean(b) => ExtensionValue::Boolean(b),
+                    AvroAttribute::Int(i) => ExtensionValue::Integer(i64::from(i)),
+                    AvroAttribute::String(s) => ExtensionValue::String(s),
+                    AvroAttribute::Bytes(b) => ExtensionValue::Binary(b),
+                };
+                serializer.set_extension(&name, value)?
+            };
+        }
+
+        let data = match self.data {
+            AvroData::Bytes(bytes) => Data::Binary(bytes),
+            AvroData::Null => return serializer.end(),
+            AvroData::Boolean(b) => Data::Object(Value::Bool(b)),
+            AvroData::Map(members) => Data::Object(Value::Object(members)),
+            AvroData::Array(items) => Data::Object(Value::Array(items)),
+            AvroData::Double(d) => Data::Object(number(d)?),
+            AvroData::String(text) => match datacontenttype.as_deref().map(essence) {
+                Some(t) if is_json(&t) => Data::Object(Value::String(text)),
+                _ => Data::StringOrBinary(text),
+            },
+        };
+        serializer.end_with_data(data)
+    }
+}
+
+/// Convert a double to a JSON number, as an integer when it has no fractional part.
+fn number(d: f64) -> Result<Value, Error> {
+    if d.fract() == 0.0 && d.abs() <= MAX_SAFE_INTEGER {
+        Ok(Value::from(d as i64))
+    } else {
+        Number::from_f64(d)
+            .map(Value::Number)
+            .ok_or(Error::InvalidAvroRecord("data holds a non finite number"))
+    }
+}
@@ -0,0 +1,356 @@
+use crate::{CloudEvent, Error};
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::io::{BufRead, BufReader, Read};
+use std::iter::FromIterator;
+use std::ops::Deref;
+
+/// Batch of events according to the JSON batch format
+///
+/// A batch is serialized as a JSON array of events, which may use different spec versions.
+/// Use [`Batch::reader`] to parse a batch event by event, without buffering all of it.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::{cloudevent_v0_2, cloudevent_v1_0, Batch};
+///
+/// let mut batch = Batch::new();
+/// batch.push(cloudevent_v1_0!(
+///     event_type: "test type",
+///     source: "http://www.google.com",
+///     event_id: "1",
+/// ).unwrap());
+/// batch.push(cloudevent_v0_2!(
+///     event_type: "test type",
+///     source: "http://www.google.com",
+///     event_id: "2",
+/// ).unwrap());
+///
+/// let json = batch.to_vec().unwrap();
+/// assert_eq!(Batch::from_slice(&json).unwrap(), batch);
+/// ```
+///
+/// [`Batch::reader`]: #method.reader
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct Batch(Vec<CloudEvent>);
+
+impl Batch {
+    /// Create an empty batch.
+    pub fn new() -> Self {
+        Batch(Vec::new())
+    }
+
+    /// Add an event to the batch.
+    pub fn push<E: Into<CloudEvent>>(&mut self, event: E) {
+        self.0.push(event.into());
+    }
+
+    /// Get the events of the batch.
+    pub fn into_events(self) -> Vec<CloudEvent> {
+        self.0
+    }
+
+    /// Serialize the batch in the JSON batch format.
+    pub fn to_vec(&self) -> Result<Vec<u8>, Error> {
+        Ok(serde_json::to_vec(self)?)
+    }
+
+    /// Parse a batch in the JSON batch format.
+    ///
+    /// # Errors
+    ///
+    /// An [`Error::InvalidBatch`] is returned if the input is not a JSON array, and an
+    /// [`Error::InvalidBatchEvent`] holding the index of the element if one of its elements
+    /// is not a valid event.
+    ///
+    /// [`Error::InvalidBatch`]: enum.Error.html#variant.InvalidBatch
+    /// [`Error::InvalidBatchEvent`]: enum.Error.html#variant.InvalidBatchEvent
+    pub fn from_slice(bytes: &[u8]) -> Result<Self, Error> {
+        Batch::reader(bytes).collect()
+    }
+
+    /// Parse a batch in the JSON batch format from an [`io::Read`].
+    ///
+    /// # Errors
+    ///
+    /// See [`from_slice`], reading from the input can fail as well.
+    ///
+    /// [`io::Read`]: https://doc.rust-lang.org/std/io/trait.Read.html
+    /// [`from_slice`]: #method.from_slice
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, Error> {
+        Batch::reader(reader).collect()
+    }
+
+    /// Parse a batch in the JSON batch format event by event.
+    ///
+    /// The input is read in chunks, and only one event is buffered at a time. An element which
+    /// is not a valid event is reported with its index, and parsing continues with the next
+    /// element. A malformed array, or anything but whitespace after its end, ends the iteration
+    /// after its error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::{Batch, Error};
+    ///
+    /// let json = r#"[
+    ///     {"specversion": "1.0", "type": "test type", "source": "http://www.google.com", "id": "1"},
+    ///     {"specversion": "1.0", "type": "test type", "source": "http://www.google.com"}
+    /// ]"#;
+    ///
+    /// let results: Vec<_> = Batch::reader(json.as_bytes()).collect();
+    /// assert!(results[0].is_ok());
+    /// assert!(matches!(results[1], Err(Error::InvalidBatchEvent { index: 1, .. })));
+    /// ```
+    pub fn reader<R: Read>(reader: R) -> BatchReader<R> {
+        BatchReader {
+            reader: BufReader::new(reader),
+            index: 0,
+            state: State::Start,
+        }
+    }
+}
+
+impl Deref for Batch {
+    type Target = [CloudEvent];
+
+    fn deref(&self) -> &[CloudEvent] {
+        &self.0
+    }
+}
+
+impl From<Vec<CloudEvent>> for Batch {
+    fn from(events: Vec<CloudEvent>) -> Self {
+        Batch(events)
+    }
+}
+
+impl FromIterator<CloudEvent> for Batch {
+    fn from_iter<I: IntoIterator<Item = CloudEvent>>(iter: I) -> Self {
+        Batch(iter.into_iter().collect())
+    }
+}
+
+impl IntoIterator for Batch {
+    type Item = CloudEvent;
+    type IntoIter = std::vec::IntoIter<CloudEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Batch {
+    type Item = &'a CloudEvent;
+    type IntoIter = std::slice::Iter<'a, CloudEvent>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl Serialize for Batch {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.0.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Batch {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(BatchVisitor)
+    }
+}
+
+struct BatchVisitor;
+
+impl<'de> Visitor<'de> for BatchVisitor {
+    type Value = Batch;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a batch of CloudEvents")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut events = Vec::with_capacity(seq.size_hint().unwrap_or_default());
+        loop {
+            let index = events.len();
+            match seq.next_element::<CloudEvent>() {
+                Ok(Some(event)) => events.push(event),
+                Ok(None) => return Ok(Batch(events)),
+                Err(e) => {
+                    return Err(de::Error::custom(format_args!(
+                        "invalid event at index {}: {}",
+                        index, e
+                    )))
+                }
+            }
+        }
+    }
+}
+
+/// Iterator over the events of a batch, created by [`Batch::reader`]
+///
+/// [`Batch::reader`]: struct.Batch.html#method.reader
+pub struct BatchReader<R> {
+    reader: BufReader<R>,
+    index: usize,
+    state: State,
+}
+
+#[derive(PartialEq)]
+enum State {
+    Start,
+    Elements,
+    End,
+    Done,
+}
+
+impl<R: Read> BatchReader<R> {
+    /// Skip whitespace, returning the next byte without consuming it.
+    fn peek_non_whitespace(&mut self) -> Result<Option<u8>, Error> {
+        loop {
+            let buf = self.reader.fill_buf()?;
+            match buf.iter().position(|b| !b.is_ascii_whitespace()) {
+                Some(i) => {
+                    let b = buf[i];
+                    self.reader.consume(i);
+                    return Ok(Some(b));
+                }
+                None if buf.is_empty() => return Ok(None),
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    /// Read the bytes of the next array element, returning them and whether more elements follow.
+    fn next_element(&mut self) -> Result<(Vec<u8>, bool), Error> {
+        let mut element = Vec::new();
+        let mut depth = 0usize;
+        let mut in_string = false;
+        let mut escaped = false;
+
+        loop {
+            let buf = self.reader.fill_buf()?;
+            if buf.is_empty() {
+                return Err(malformed("unexpected end of a batch"));
+            }
+            let mut end = None;
+            for (i, &b) in buf.iter().enumerate() {
+                if in_string {
+                    match b {
+                        _ if escaped => escaped = false,
+                        b'\\' => escaped = true,
+                        b'"' => in_string = false,
+                        _ => {}
+                    }
+                } else {
+                    match b {
+                        b'"' => in_string = true,
+                        b'{' | b'[' => depth += 1,
+                        b'}' | b']' if depth > 0 => depth -= 1,
+                        b',' if depth == 0 => end = Some((i, true)),
+                        b']' => end = Some((i, false)),
+                        _ => {}
+                    }
+                    if end.is_some() {
+                        break;
+                    }
+                }
+            }
+            match end {
+                Some((i, more)) => {
+                    element.extend_from_slice(&buf[..i]);
+                    self.reader.consume(i + 1);
+                    return Ok((element, more));
+                }
+                None => {
+                    element.extend_from_slice(buf);
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    fn read_next(&mut self) -> Result<Option<CloudEvent>, Error> {
+        match self.state {
+            State::Start => {
+                if self.peek_non_whitespace()? != Some(b'[') {
+                    return Err(malformed("a batch must be a JSON array"));
+                }
+                self.reader.consume(1);
+                if self.peek_non_whitespace()? == Some(b']') {
+                    self.reader.consume(1);
+                    self.state = State::End;
+                    return self.read_next();
+                }
+                self.state = State::Elements;
+            }
+            State::End => {
+                self.state = State::Done;
+                return match self.peek_non_whitespace()? {
+                    Some(_) => Err(malformed("unexpected input after a batch")),
+                    None => Ok(None),
+                };
+            }
+            State::Elements | State::Done => {}
+        }
+
+        let (element, more) = self.next_element()?;
+        if element.iter().all(u8::is_ascii_whitespace) {
+            return Err(malformed(if more {
+                "missing event in a batch"
+            } else {
+                "trailing comma in a batch"
+            }));
+        }
+        if !more {
+            self.state = State::End;
+        }
+        let index = self.index;
+        self.index += 1;
+        serde_json::from_slice(&element)
+            .map(Some)
+            .map_err(|source| Error::InvalidBatchEvent { index, source })
+    }
+}
+
+impl<R: Read> Iterator for BatchReader<R> {
+    type Item = Result<CloudEvent, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.state == State::Done {
+            return None;
+        }
+        match self.read_next() {
+            Ok(Some(event)) => Some(Ok(event)),
+            Ok(None) => {
+                self.state = State::Done;
+                None
+            }
+            Err(e @ Error::InvalidBatchEvent { .. }) => Some(Err(e)),
+            Err(e) => {
+                self.state = State::Done;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn malformed(reason: &'static str) -> Error {
+    Error::InvalidBatch(reason)
+}
@@ -1,4 +1,5 @@
 use crate::v0_2::CloudEventV0_2Builder;
+use crate::v0_3::CloudEventV0_3Builder;
 use crate::v1_0::CloudEventV1_0Builder;
 use crate::CloudEventLatestBuilder;
 
@@ -27,6 +28,10 @@ impl CloudEventBuilder {
     pub fn v0_2() -> CloudEventV0_2Builder {
         CloudEventV0_2Builder::default()
     }
+    /// Create a new `CloudEvent` according to spec version 0.3
+    pub fn v0_3() -> CloudEventV0_3Builder {
+        CloudEventV0_3Builder::default()
+    }
     /// Create a new `CloudEvent` according to spec version 1.0
     pub fn v1_0() -> CloudEventV1_0Builder {
         CloudEventV1_0Builder::default()
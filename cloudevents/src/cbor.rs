@@ -0,0 +1,49 @@
+/*!
+CBOR structured encoding, available with the `cbor` feature.
+
+Serializes events in CBOR (see [RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) with the same model
+as the JSON format: a map of the context attributes, the extensions and the data. As CBOR is not human
+readable, binary data and binary extensions are encoded as native byte strings instead of base64 strings,
+and binary data of spec version 1.0 is carried in `data` rather than `data_base64`.
+
+# Example
+
+```
+use cloudevents::cbor::{from_bytes, to_bytes};
+use cloudevents::{cloudevent, CloudEvent, Data};
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    data: Data::from_binary(b"\x00\x01"),
+).unwrap().into();
+
+let bytes = to_bytes(&event).unwrap();
+assert_eq!(from_bytes(&bytes).unwrap(), event);
+```
+*/
+use crate::{CloudEvent, Error};
+
+/// Content type of a structured mode message in CBOR.
+pub const CLOUDEVENTS_CBOR: &str = "application/cloudevents+cbor";
+
+/// Serialize the event in CBOR.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_bytes(event: &CloudEvent) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(event, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Parse an event in CBOR.
+///
+/// # Errors
+///
+/// An error is returned if the input is not valid CBOR, or if it does not carry a valid event.
+pub fn from_bytes(bytes: &[u8]) -> Result<CloudEvent, Error> {
+    Ok(ciborium::de::from_reader(bytes)?)
+}
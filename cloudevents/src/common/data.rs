@@ -1,17 +1,34 @@
-use base64;
-use failure::Error;
-use serde::ser::Serialize;
-use serde_derive::{Deserialize, Serialize};
+use crate::message::{essence, is_json};
+use crate::Error;
+use serde::de::value::{
+    MapAccessDeserializer, SeqAccessDeserializer, SeqDeserializer, StrDeserializer,
+};
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Unexpected, Visitor,
+};
+use serde::ser::{Serialize, Serializer};
+use serde_derive::Serialize;
+use serde_json::value::RawValue;
 use serde_json::Value;
+use std::borrow::Cow;
+use std::fmt;
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
+#[derive(Debug, Serialize, PartialEq, Clone)]
 #[serde(untagged)]
 /// Possible data values
+///
+/// When deserializing, strings are read as [`StringOrBinary`], byte strings of formats
+/// supporting them, such as CBOR, as [`Binary`], and any other value as [`Object`].
+///
+/// [`StringOrBinary`]: #variant.StringOrBinary
+/// [`Binary`]: #variant.Binary
+/// [`Object`]: #variant.Object
 pub enum Data {
-    /// Represents a string or binary value. As a binary value is base64 encoded,
-    /// it is impossible to determine if the value is a string or a binary value.
-    /// It is up to the client to determine the data type, and do the required processing
-    /// of the [`String`] value.
+    /// Represents a string value. Formats without a way to mark binary data, such as
+    /// the v0.2 JSON format, deserialize base64 encoded binary data into this variant,
+    /// in which case it is up to the client to determine the data type and do the
+    /// required processing of the [`String`] value.
     ///
     /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
     StringOrBinary(String),
@@ -19,6 +36,90 @@ pub enum Data {
     ///
     /// [`Value`]: https://docs.serde.rs/serde_json/value/enum.Value.html
     Object(Value),
+    /// Represents a binary value. In the v1.0 JSON format it is serialized as
+    /// base64 encoded `data_base64`, in formats which are not human readable as a byte string.
+    #[serde(serialize_with = "serialize_binary")]
+    Binary(Vec<u8>),
+}
+
+fn serialize_binary<S>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&base64::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+impl<'de> Deserialize<'de> for Data {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(DataVisitor)
+    }
+}
+
+struct DataVisitor;
+
+impl<'de> Visitor<'de> for DataVisitor {
+    type Value = Data;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("event data")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Data::Object(Value::Bool(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Data::Object(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Data::Object(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Data::Object(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Data::StringOrBinary(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Data::StringOrBinary(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Data::Binary(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Data::Binary(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Data::Object(Value::Null))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Value::deserialize(SeqAccessDeserializer::new(seq)).map(Data::Object)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Value::deserialize(MapAccessDeserializer::new(map)).map(Data::Object)
+    }
 }
 
 impl Data {
@@ -50,7 +151,7 @@ impl Data {
     /// use cloudevents::Data;
     ///
     /// let value = Data::from_binary(b"value");
-    /// assert_eq!(value, Data::StringOrBinary("dmFsdWU=".to_owned()));
+    /// assert_eq!(value, Data::Binary(b"value".to_vec()));
     /// ```
     ///
     /// [`AsRef<[u8]>`]: https://doc.rust-lang.org/std/convert/trait.AsRef.html
@@ -59,7 +160,7 @@ impl Data {
     where
         I: AsRef<[u8]>,
     {
-        Data::StringOrBinary(base64::encode(&i))
+        Data::Binary(i.as_ref().to_vec())
     }
 
     /// Create a [`Data`] from a [`Serialize`] object.
@@ -86,4 +187,82 @@ impl Data {
     {
         Ok(Data::Object(serde_json::to_value(v)?))
     }
+
+    /// Decode the data into `T` according to the content type of the event.
+    ///
+    /// Without a content type, the data is JSON, as in the JSON format. `content_encoding`
+    /// is the `datacontentencoding` of spec version 0.3, whose `base64` value marks
+    /// string data as base64 encoded binary data.
+    pub(crate) fn decode<T>(
+        &self,
+        content_type: Option<&str>,
+        content_encoding: Option<&str>,
+    ) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let payload = match self {
+            Data::StringOrBinary(encoded) if matches!(content_encoding, Some(e) if e.eq_ignore_ascii_case("base64")) =>
+            {
+                let bytes = base64::decode(encoded).map_err(|source| Error::InvalidBase64 {
+                    attribute: "data",
+                    source,
+                })?;
+                Payload::Bytes(Cow::Owned(bytes))
+            }
+            Data::StringOrBinary(text) => Payload::Text(text),
+            Data::Object(value) => Payload::Value(value),
+            Data::Binary(bytes) => Payload::Bytes(Cow::Borrowed(bytes)),
+        };
+
+        payload.decode(content_type)
+    }
+}
+
+/// Data to decode, with base64 encoded binary data decoded.
+pub(crate) enum Payload<'a> {
+    Value(&'a Value),
+    /// JSON other than a string, as read from the JSON format.
+    Json(&'a RawValue),
+    Text(&'a str),
+    Bytes(Cow<'a, [u8]>),
+}
+
+impl<'a> Payload<'a> {
+    /// Decode the payload into `T` according to the content type of the event.
+    pub(crate) fn decode<T>(self, content_type: Option<&str>) -> Result<T, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let media_type = content_type.map_or_else(|| "application/json".to_owned(), essence);
+        if is_json(&media_type) {
+            Ok(match self {
+                Payload::Value(value) => T::deserialize(value)?,
+                Payload::Json(raw) => serde_json::from_str(raw.get())?,
+                Payload::Text(text) => serde_json::from_str(text)?,
+                Payload::Bytes(bytes) => serde_json::from_slice(&bytes)?,
+            })
+        } else if media_type.starts_with("text/") {
+            let text = match &self {
+                Payload::Value(value) => return Ok(T::deserialize(*value)?),
+                Payload::Json(raw) => return Ok(serde_json::from_str(raw.get())?),
+                Payload::Text(text) => text,
+                Payload::Bytes(bytes) => std::str::from_utf8(bytes).map_err(|_| {
+                    <serde_json::Error as de::Error>::invalid_value(
+                        Unexpected::Bytes(bytes),
+                        &"UTF-8 text",
+                    )
+                })?,
+            };
+            let deserializer: StrDeserializer<serde_json::Error> = text.into_deserializer();
+            Ok(T::deserialize(deserializer)?)
+        } else if let Payload::Bytes(bytes) = self {
+            let deserializer = SeqDeserializer::<_, serde_json::Error>::new(bytes.iter().copied());
+            Ok(T::deserialize(deserializer)?)
+        } else {
+            Err(Error::UnsupportedContentType(
+                content_type.unwrap_or_default().to_owned(),
+            ))
+        }
+    }
 }
@@ -1,17 +1,51 @@
-use failure::Error;
-use serde::ser::Serialize;
-use serde_derive::{Deserialize, Serialize};
+use crate::message::format_time;
+use crate::Error;
+use chrono::prelude::{DateTime, FixedOffset, Utc};
+use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
+use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
 use serde_json::Value;
+use std::convert::TryFrom;
+use std::fmt;
+use url::Url;
 
-#[derive(Debug, Deserialize, Serialize, PartialEq, Clone)]
-#[serde(untagged)]
 /// Possible extension values
+///
+/// Besides [`Object`], the variants map to the types of the CloudEvents type system.
+/// The [`Display`] implementation returns the canonical string encoding of the value,
+/// as used by transports which only support string attributes.
+///
+/// When deserializing from JSON, booleans, integers and strings are read as [`Boolean`],
+/// [`Integer`] and [`String`], any other value is read as [`Object`]. URIs, URI-references,
+/// timestamps and binary values are encoded as strings, as the JSON format does not
+/// distinguish them from other strings.
+///
+/// [`Object`]: #variant.Object
+/// [`Boolean`]: #variant.Boolean
+/// [`Integer`]: #variant.Integer
+/// [`String`]: #variant.String
+/// [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+#[derive(Debug, PartialEq, Clone)]
 pub enum ExtensionValue {
+    /// Represents a `Boolean` value.
+    Boolean(bool),
+    /// Represents an `Integer` value. Spec version 1.0 restricts integers to 32 bits.
+    Integer(i64),
     /// Represents a [`String`] value.
     ///
     /// [`String`]: https://doc.rust-lang.org/std/string/struct.String.html
     String(String),
-    /// Represents a JSON [`Value`].
+    /// Represents a `Binary` value.
+    Binary(Vec<u8>),
+    /// Represents an absolute `URI` value.
+    Uri(Url),
+    /// Represents a `URI-reference` value.
+    UriRef(String),
+    /// Represents a `Timestamp` value.
+    Timestamp(DateTime<FixedOffset>),
+    /// Represents a JSON [`Value`]. Only allowed in spec versions before 1.0, which do not
+    /// restrict extensions to the CloudEvents type system, except for strings, booleans and
+    /// 32-bit integers.
     ///
     /// [`Value`]: https://docs.serde.rs/serde_json/value/enum.Value.html
     Object(Value),
@@ -62,4 +96,213 @@ impl ExtensionValue {
     {
         Ok(ExtensionValue::Object(serde_json::to_value(s)?))
     }
+
+    /// Create an [`ExtensionValue`] holding a `URI-reference`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::ExtensionValue;
+    ///
+    /// let value = ExtensionValue::from_uri_ref("/cloudevents/spec");
+    /// assert_eq!(value, ExtensionValue::UriRef("/cloudevents/spec".to_owned()));
+    /// ```
+    ///
+    /// [`ExtensionValue`]: enum.ExtensionValue.html
+    pub fn from_uri_ref<S>(s: S) -> Self
+    where
+        S: Into<String>,
+    {
+        ExtensionValue::UriRef(s.into())
+    }
+
+    /// Check if the value is a nested JSON object or array, which cannot be
+    /// represented in the CloudEvents type system.
+    pub(crate) fn is_nested(&self) -> bool {
+        match self {
+            ExtensionValue::Object(v) => v.is_object() || v.is_array(),
+            _ => false,
+        }
+    }
+
+    /// Check if the value is part of the type system of spec version 1.0, whose integers
+    /// are 32-bit. JSON values are only allowed if they are strings, booleans or integers.
+    pub(crate) fn is_in_type_system_v1_0(&self) -> bool {
+        match self {
+            ExtensionValue::Integer(i) => i32::try_from(*i).is_ok(),
+            ExtensionValue::Object(Value::String(_)) | ExtensionValue::Object(Value::Bool(_)) => {
+                true
+            }
+            ExtensionValue::Object(Value::Number(n)) => {
+                matches!(n.as_i64(), Some(i) if i32::try_from(i).is_ok())
+            }
+            ExtensionValue::Object(_) => false,
+            _ => true,
+        }
+    }
+}
+
+impl fmt::Display for ExtensionValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExtensionValue::Boolean(b) => write!(f, "{}", b),
+            ExtensionValue::Integer(i) => write!(f, "{}", i),
+            ExtensionValue::String(s) => f.write_str(s),
+            ExtensionValue::Binary(b) => f.write_str(&base64::encode(b)),
+            ExtensionValue::Uri(u) => f.write_str(u.as_str()),
+            ExtensionValue::UriRef(u) => f.write_str(u),
+            ExtensionValue::Timestamp(t) => f.write_str(&format_time(t)),
+            ExtensionValue::Object(Value::String(s)) => f.write_str(s),
+            ExtensionValue::Object(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<bool> for ExtensionValue {
+    fn from(b: bool) -> Self {
+        ExtensionValue::Boolean(b)
+    }
+}
+
+impl From<i32> for ExtensionValue {
+    fn from(i: i32) -> Self {
+        ExtensionValue::Integer(i64::from(i))
+    }
+}
+
+impl From<i64> for ExtensionValue {
+    fn from(i: i64) -> Self {
+        ExtensionValue::Integer(i)
+    }
+}
+
+impl From<&str> for ExtensionValue {
+    fn from(s: &str) -> Self {
+        ExtensionValue::String(s.to_owned())
+    }
+}
+
+impl From<String> for ExtensionValue {
+    fn from(s: String) -> Self {
+        ExtensionValue::String(s)
+    }
+}
+
+impl From<Vec<u8>> for ExtensionValue {
+    fn from(b: Vec<u8>) -> Self {
+        ExtensionValue::Binary(b)
+    }
+}
+
+impl From<&[u8]> for ExtensionValue {
+    fn from(b: &[u8]) -> Self {
+        ExtensionValue::Binary(b.to_vec())
+    }
+}
+
+impl From<Url> for ExtensionValue {
+    fn from(u: Url) -> Self {
+        ExtensionValue::Uri(u)
+    }
+}
+
+impl From<DateTime<FixedOffset>> for ExtensionValue {
+    fn from(t: DateTime<FixedOffset>) -> Self {
+        ExtensionValue::Timestamp(t)
+    }
+}
+
+impl From<DateTime<Utc>> for ExtensionValue {
+    fn from(t: DateTime<Utc>) -> Self {
+        ExtensionValue::Timestamp(t.into())
+    }
+}
+
+impl Serialize for ExtensionValue {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ExtensionValue::Boolean(b) => serializer.serialize_bool(*b),
+            ExtensionValue::Integer(i) => serializer.serialize_i64(*i),
+            ExtensionValue::Binary(b) if !serializer.is_human_readable() => {
+                serializer.serialize_bytes(b)
+            }
+            ExtensionValue::Object(v) => v.serialize(serializer),
+            v => serializer.collect_str(v),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ExtensionValue {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ExtensionValueVisitor)
+    }
+}
+
+struct ExtensionValueVisitor;
+
+impl<'de> Visitor<'de> for ExtensionValueVisitor {
+    type Value = ExtensionValue;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an extension attribute value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Boolean(v))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Integer(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(match i64::try_from(v) {
+            Ok(i) => ExtensionValue::Integer(i),
+            Err(_) => ExtensionValue::Object(Value::from(v)),
+        })
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Object(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::String(v.to_owned()))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::String(v))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Binary(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Binary(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(ExtensionValue::Object(Value::Null))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Value::deserialize(SeqAccessDeserializer::new(seq)).map(ExtensionValue::Object)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Value::deserialize(MapAccessDeserializer::new(map)).map(ExtensionValue::Object)
+    }
 }
@@ -1,5 +1,8 @@
 mod data;
 mod extension;
+mod spec_version;
 
 pub use data::Data;
+pub(crate) use data::Payload;
 pub use extension::ExtensionValue;
+pub use spec_version::SpecVersion;
@@ -0,0 +1,113 @@
+use crate::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Supported CloudEvents spec versions
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum SpecVersion {
+    /// Spec version 0.2
+    V0_2,
+    /// Spec version 0.3
+    V0_3,
+    /// Spec version 1.0
+    V1_0,
+}
+
+impl SpecVersion {
+    /// Get the value of the `specversion` attribute for this version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::SpecVersion;
+    ///
+    /// assert_eq!(SpecVersion::V1_0.as_str(), "1.0");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SpecVersion::V0_2 => "0.2",
+            SpecVersion::V0_3 => "0.3",
+            SpecVersion::V1_0 => "1.0",
+        }
+    }
+
+    /// Get the names of the context attributes defined by this version.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::SpecVersion;
+    ///
+    /// assert!(SpecVersion::V1_0.attribute_names().contains(&"dataschema"));
+    /// assert!(!SpecVersion::V0_2.attribute_names().contains(&"subject"));
+    /// ```
+    pub fn attribute_names(&self) -> &'static [&'static str] {
+        match self {
+            SpecVersion::V0_2 => &[
+                "specversion",
+                "id",
+                "type",
+                "source",
+                "contenttype",
+                "schemaurl",
+                "time",
+            ],
+            SpecVersion::V0_3 => &[
+                "specversion",
+                "id",
+                "type",
+                "source",
+                "datacontenttype",
+                "datacontentencoding",
+                "schemaurl",
+                "subject",
+                "time",
+            ],
+            SpecVersion::V1_0 => &[
+                "specversion",
+                "id",
+                "type",
+                "source",
+                "datacontenttype",
+                "dataschema",
+                "subject",
+                "time",
+            ],
+        }
+    }
+
+    /// Get the name of the attribute holding the content type of the data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::SpecVersion;
+    ///
+    /// assert_eq!(SpecVersion::V0_2.content_type_attribute(), "contenttype");
+    /// ```
+    pub fn content_type_attribute(&self) -> &'static str {
+        match self {
+            SpecVersion::V0_2 => "contenttype",
+            SpecVersion::V0_3 | SpecVersion::V1_0 => "datacontenttype",
+        }
+    }
+}
+
+impl fmt::Display for SpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SpecVersion {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "0.2" => Ok(SpecVersion::V0_2),
+            "0.3" => Ok(SpecVersion::V0_3),
+            "1.0" => Ok(SpecVersion::V1_0),
+            _ => Err(Error::UnknownSpecVersion(s.to_owned())),
+        }
+    }
+}
@@ -0,0 +1,252 @@
+use crate::v0_2::CloudEventV0_2;
+use crate::v0_3::CloudEventV0_3;
+use crate::v1_0::CloudEventV1_0;
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// Fail if any of the given attributes is populated, as it has no
+/// counterpart in the target spec version. When converting to spec version 1.0,
+/// also fail for the extensions whose value is outside of its type system.
+fn ensure_representable(
+    target: SpecVersion,
+    attributes: &[(&'static str, bool)],
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) -> Result<(), Error> {
+    let mut unrepresentable: Vec<String> = attributes
+        .iter()
+        .filter(|(_, populated)| *populated)
+        .map(|(name, _)| (*name).to_owned())
+        .collect();
+    if target == SpecVersion::V1_0 {
+        let mut names: Vec<String> = extensions
+            .into_iter()
+            .flatten()
+            .filter(|(_, value)| !value.is_in_type_system_v1_0())
+            .map(|(name, _)| name.clone())
+            .collect();
+        names.sort();
+        unrepresentable.extend(names);
+    }
+    if unrepresentable.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::UnrepresentableAttributes {
+            version: target,
+            attributes: unrepresentable,
+        })
+    }
+}
+
+/// Check if the v0.3 datacontentencoding marks the data as base64 encoded binary data,
+/// which the other spec versions represent as binary data without an encoding.
+fn is_base64(datacontentencoding: Option<&str>) -> bool {
+    matches!(datacontentencoding, Some(e) if e.eq_ignore_ascii_case("base64"))
+}
+
+/// Decode base64 encoded v0.3 string data into binary data.
+fn decode_base64(data: Option<Data>) -> Result<Option<Data>, Error> {
+    Ok(match data {
+        Some(Data::StringOrBinary(encoded)) => Some(Data::Binary(
+            base64::decode(&encoded).map_err(|source| Error::InvalidBase64 {
+                attribute: "data",
+                source,
+            })?,
+        )),
+        data => data,
+    })
+}
+
+impl From<CloudEventV0_2> for CloudEventV0_3 {
+    fn from(e: CloudEventV0_2) -> Self {
+        CloudEventV0_3::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            None,
+            e.schemaurl,
+            e.contenttype,
+            None,
+            e.data,
+            e.extensions,
+        )
+    }
+}
+
+impl TryFrom<CloudEventV0_2> for CloudEventV1_0 {
+    type Error = Error;
+
+    fn try_from(e: CloudEventV0_2) -> Result<Self, Self::Error> {
+        ensure_representable(SpecVersion::V1_0, &[], e.extensions.as_ref())?;
+        Ok(CloudEventV1_0::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            None,
+            e.schemaurl,
+            e.contenttype,
+            e.data,
+            e.extensions,
+        ))
+    }
+}
+
+impl TryFrom<CloudEventV0_3> for CloudEventV0_2 {
+    type Error = Error;
+
+    fn try_from(e: CloudEventV0_3) -> Result<Self, Self::Error> {
+        let base64 = is_base64(e.datacontentencoding.as_deref());
+        ensure_representable(
+            SpecVersion::V0_2,
+            &[
+                ("subject", e.subject.is_some()),
+                (
+                    "datacontentencoding",
+                    e.datacontentencoding.is_some() && !base64,
+                ),
+            ],
+            None,
+        )?;
+        let data = if base64 {
+            decode_base64(e.data)?
+        } else {
+            e.data
+        };
+        Ok(CloudEventV0_2::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            e.schemaurl,
+            e.datacontenttype,
+            data,
+            e.extensions,
+        ))
+    }
+}
+
+impl TryFrom<CloudEventV0_3> for CloudEventV1_0 {
+    type Error = Error;
+
+    fn try_from(e: CloudEventV0_3) -> Result<Self, Self::Error> {
+        let base64 = is_base64(e.datacontentencoding.as_deref());
+        ensure_representable(
+            SpecVersion::V1_0,
+            &[(
+                "datacontentencoding",
+                e.datacontentencoding.is_some() && !base64,
+            )],
+            e.extensions.as_ref(),
+        )?;
+        let data = if base64 {
+            decode_base64(e.data)?
+        } else {
+            e.data
+        };
+        Ok(CloudEventV1_0::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            e.subject,
+            e.schemaurl,
+            e.datacontenttype,
+            data,
+            e.extensions,
+        ))
+    }
+}
+
+impl TryFrom<CloudEventV1_0> for CloudEventV0_2 {
+    type Error = Error;
+
+    fn try_from(e: CloudEventV1_0) -> Result<Self, Self::Error> {
+        ensure_representable(SpecVersion::V0_2, &[("subject", e.subject.is_some())], None)?;
+        Ok(CloudEventV0_2::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            e.dataschema,
+            e.datacontenttype,
+            e.data,
+            e.extensions,
+        ))
+    }
+}
+
+impl From<CloudEventV1_0> for CloudEventV0_3 {
+    fn from(e: CloudEventV1_0) -> Self {
+        CloudEventV0_3::new(
+            e.event_type,
+            e.source,
+            e.id,
+            e.time,
+            e.subject,
+            e.dataschema,
+            e.datacontenttype,
+            match e.data {
+                Some(Data::Binary(_)) => Some(String::from("base64")),
+                _ => None,
+            },
+            e.data,
+            e.extensions,
+        )
+    }
+}
+
+impl From<CloudEventV0_2> for CloudEvent {
+    fn from(e: CloudEventV0_2) -> Self {
+        CloudEvent::V0_2(e)
+    }
+}
+
+impl From<CloudEventV0_3> for CloudEvent {
+    fn from(e: CloudEventV0_3) -> Self {
+        CloudEvent::V0_3(e)
+    }
+}
+
+impl From<CloudEventV1_0> for CloudEvent {
+    fn from(e: CloudEventV1_0) -> Self {
+        CloudEvent::V1_0(e)
+    }
+}
+
+impl TryFrom<CloudEvent> for CloudEventV0_2 {
+    type Error = Error;
+
+    fn try_from(e: CloudEvent) -> Result<Self, Self::Error> {
+        match e {
+            CloudEvent::V0_2(e) => Ok(e),
+            CloudEvent::V0_3(e) => CloudEventV0_2::try_from(e),
+            CloudEvent::V1_0(e) => CloudEventV0_2::try_from(e),
+        }
+    }
+}
+
+impl TryFrom<CloudEvent> for CloudEventV0_3 {
+    type Error = Error;
+
+    fn try_from(e: CloudEvent) -> Result<Self, Self::Error> {
+        Ok(match e {
+            CloudEvent::V0_2(e) => CloudEventV0_3::from(e),
+            CloudEvent::V0_3(e) => e,
+            CloudEvent::V1_0(e) => CloudEventV0_3::from(e),
+        })
+    }
+}
+
+impl TryFrom<CloudEvent> for CloudEventV1_0 {
+    type Error = Error;
+
+    fn try_from(e: CloudEvent) -> Result<Self, Self::Error> {
+        match e {
+            CloudEvent::V0_2(e) => CloudEventV1_0::try_from(e),
+            CloudEvent::V0_3(e) => CloudEventV1_0::try_from(e),
+            CloudEvent::V1_0(e) => Ok(e),
+        }
+    }
+}
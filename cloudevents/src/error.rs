@@ -0,0 +1,163 @@
+use crate::SpecVersion;
+use thiserror::Error;
+
+/// Errors returned when building, converting or parsing a CloudEvent
+#[derive(Debug, Error)]
+#[non_exhaustive]
+pub enum Error {
+    /// A required attribute is not populated.
+    #[error("Attribute {0} is required")]
+    MissingRequiredAttribute(&'static str),
+    /// A string attribute is populated with an empty string.
+    #[error("Attribute {0} must not be empty")]
+    EmptyAttribute(&'static str),
+    /// An attribute which must be a URI or URI-reference cannot be parsed.
+    #[error("Attribute {attribute} is not a valid URI: {source}")]
+    InvalidUri {
+        attribute: &'static str,
+        source: url::ParseError,
+    },
+    /// The time attribute is not a valid RFC 3339 timestamp.
+    #[error("Attribute time is not a valid RFC 3339 timestamp: {source}")]
+    InvalidTime { source: chrono::ParseError },
+    /// A content type attribute is not a media type according to RFC 2046.
+    #[error("Attribute {attribute} is not a valid media type: {value}")]
+    InvalidMediaType {
+        attribute: &'static str,
+        value: String,
+    },
+    /// A content encoding attribute is not an encoding according to RFC 2045.
+    #[error("Attribute {attribute} is not a valid content encoding: {value}")]
+    InvalidContentEncoding {
+        attribute: &'static str,
+        value: String,
+    },
+    /// A content type attribute contradicts the data, e.g. JSON data with a `text/plain` content type.
+    #[error("Attribute {attribute} does not match the data: {value}")]
+    DataContentTypeMismatch {
+        attribute: &'static str,
+        value: String,
+    },
+    /// An attribute is not defined by the spec version.
+    #[error("Unknown attribute: {0}")]
+    UnknownAttribute(String),
+    /// The spec version is not supported by this crate.
+    #[error("Unknown spec version: {0}")]
+    UnknownSpecVersion(String),
+    /// An event of another spec version than expected is read.
+    #[error("Expected spec version {expected}, found {found}")]
+    SpecVersionMismatch {
+        expected: SpecVersion,
+        found: SpecVersion,
+    },
+    /// An extension name does not follow the naming rules of the spec version.
+    #[error("Invalid extension name: {0}")]
+    InvalidExtensionName(String),
+    /// An extension value cannot be represented in the spec version.
+    #[error("Extension {0} has a value which is not supported by the spec version")]
+    InvalidExtensionValue(String),
+    /// Base64 encoded data cannot be decoded.
+    #[error("Attribute {attribute} is not valid base64: {source}")]
+    InvalidBase64 {
+        attribute: &'static str,
+        source: base64::DecodeError,
+    },
+    /// Populated attributes, or extensions with a value outside of the type system,
+    /// have no counterpart in the target spec version.
+    #[error("Attributes cannot be represented in spec version {version}: {}", attributes.join(", "))]
+    UnrepresentableAttributes {
+        version: SpecVersion,
+        attributes: Vec<String>,
+    },
+    /// The input of a batch is not a JSON array.
+    #[error("Invalid batch: {0}")]
+    InvalidBatch(&'static str),
+    /// An element of a batch is not a valid event.
+    #[error("Invalid event at index {index} of the batch: {source}")]
+    InvalidBatchEvent {
+        index: usize,
+        source: serde_json::Error,
+    },
+    /// A header of a protocol binding message is not a valid percent-encoded UTF-8 value.
+    #[error("Header {0} has an invalid value")]
+    InvalidHeaderValue(String),
+    /// The content type of a protocol binding message, or of data to decode, is not supported.
+    #[error("Unsupported content type: {0}")]
+    UnsupportedContentType(String),
+    /// An attribute of an event format message has no value, or a value of an unexpected type.
+    #[error("Attribute {0} has an invalid value")]
+    InvalidAttributeValue(String),
+    /// An AMQP message is not a valid sequence of message sections.
+    #[error("Invalid AMQP message: {0}")]
+    InvalidAmqpMessage(&'static str),
+    /// A record in the Avro format does not match the schema of the format.
+    #[error("Invalid Avro record: {0}")]
+    InvalidAvroRecord(&'static str),
+    /// An XML document does not carry an event according to the XML format.
+    #[error("Invalid XML document: {0}")]
+    InvalidXmlDocument(&'static str),
+    /// An HTTP message cannot be built.
+    #[cfg(feature = "http")]
+    #[error("HTTP error: {0}")]
+    Http(#[from] http::Error),
+    /// A message in the Protobuf format cannot be decoded.
+    #[cfg(feature = "protobuf")]
+    #[error("Protobuf error: {0}")]
+    Protobuf(#[from] prost::DecodeError),
+    /// An event cannot be encoded in CBOR.
+    #[cfg(feature = "cbor")]
+    #[error("CBOR encoding error: {0}")]
+    CborEncode(#[from] ciborium::ser::Error<std::io::Error>),
+    /// A message in CBOR cannot be decoded.
+    #[cfg(feature = "cbor")]
+    #[error("CBOR decoding error: {0}")]
+    CborDecode(#[from] ciborium::de::Error<std::io::Error>),
+    /// An event cannot be encoded in MessagePack.
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack encoding error: {0}")]
+    MessagePackEncode(#[from] rmp_serde::encode::Error),
+    /// A message in MessagePack cannot be decoded.
+    #[cfg(feature = "msgpack")]
+    #[error("MessagePack decoding error: {0}")]
+    MessagePackDecode(#[from] rmp_serde::decode::Error),
+    /// A document in the XML format is not well-formed.
+    #[cfg(feature = "xml")]
+    #[error("XML error: {0}")]
+    Xml(#[from] quick_xml::Error),
+    /// Reading the input failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+    /// A value cannot be serialized to or deserialized from JSON.
+    #[error("JSON error: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+impl Error {
+    /// Get the name of the attribute the error refers to, if any.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::Error;
+    ///
+    /// let error = Error::MissingRequiredAttribute("id");
+    /// assert_eq!(error.attribute(), Some("id"));
+    /// ```
+    pub fn attribute(&self) -> Option<&str> {
+        match self {
+            Error::MissingRequiredAttribute(attribute)
+            | Error::EmptyAttribute(attribute)
+            | Error::InvalidUri { attribute, .. }
+            | Error::InvalidMediaType { attribute, .. }
+            | Error::InvalidContentEncoding { attribute, .. }
+            | Error::DataContentTypeMismatch { attribute, .. }
+            | Error::InvalidBase64 { attribute, .. } => Some(attribute),
+            Error::InvalidTime { .. } => Some("time"),
+            Error::UnknownAttribute(name)
+            | Error::InvalidExtensionName(name)
+            | Error::InvalidExtensionValue(name)
+            | Error::InvalidAttributeValue(name) => Some(name),
+            _ => None,
+        }
+    }
+}
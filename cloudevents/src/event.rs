@@ -1,11 +1,379 @@
 use crate::v0_2::CloudEventV0_2;
+use crate::v0_3::CloudEventV0_3;
 use crate::v1_0::CloudEventV1_0;
-use serde_derive::{Deserialize, Serialize};
+use crate::{Error, SpecVersion, Validate};
+use serde::de::value::{MapAccessDeserializer, MapDeserializer, SeqAccessDeserializer};
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, IntoDeserializer, MapAccess, SeqAccess,
+    Visitor,
+};
+use serde::forward_to_deserialize_any;
+use serde_derive::Serialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
+
+/// Members of the JSON format which are not context attributes nor extensions.
+const DATA_MEMBERS: &[&str] = &["data", "data_base64"];
 
 /// Generic CloudEvent wrapping all spec versions
-#[derive(Debug, Serialize, Deserialize, Clone)]
+///
+/// When deserializing, the `specversion` attribute is read first to determine which
+/// spec version the remaining attributes are deserialized with.
+#[derive(Debug, Serialize, PartialEq, Clone)]
 #[serde(untagged)]
 pub enum CloudEvent {
     V1_0(CloudEventV1_0),
+    V0_3(CloudEventV0_3),
     V0_2(CloudEventV0_2),
 }
+
+impl CloudEvent {
+    /// Get the spec version of the wrapped event
+    pub fn spec_version(&self) -> SpecVersion {
+        match self {
+            CloudEvent::V0_2(_) => SpecVersion::V0_2,
+            CloudEvent::V0_3(_) => SpecVersion::V0_3,
+            CloudEvent::V1_0(_) => SpecVersion::V1_0,
+        }
+    }
+
+    /// Decode the data of the wrapped event into `T` according to its content type.
+    ///
+    /// See [`CloudEventV1_0::data_as`] for how the data is decoded. The content type
+    /// is read from `datacontenttype`, or `contenttype` in spec version 0.2.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::{cloudevent_v0_3, CloudEvent, Data};
+    ///
+    /// let event = CloudEvent::V0_3(cloudevent_v0_3!(
+    ///     event_type: "test type",
+    ///     source: "http://www.google.com",
+    ///     event_id: "id",
+    ///     datacontenttype: "text/plain",
+    ///     data: Data::from_binary(b"hello"),
+    /// ).unwrap());
+    ///
+    /// assert_eq!(event.data_as::<String>().unwrap(), Some("hello".to_owned()));
+    /// ```
+    ///
+    /// [`CloudEventV1_0::data_as`]: v1_0/struct.CloudEventV1_0.html#method.data_as
+    pub fn data_as<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        match self {
+            CloudEvent::V1_0(event) => event.data_as(),
+            CloudEvent::V0_3(event) => event.data_as(),
+            CloudEvent::V0_2(event) => event.data_as(),
+        }
+    }
+
+    /// Convert the event to the given spec version.
+    ///
+    /// Attributes are mapped to their counterpart in the target version
+    /// (`schemaurl` to `dataschema`, `contenttype` to `datacontenttype`, ...),
+    /// and extensions are carried over as is.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the event populates an attribute which cannot be
+    /// represented in the target version, e.g. `subject` when converting to 0.2,
+    /// or has an extension value outside of the type system of 1.0 when converting to it.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::{cloudevent_v0_2, CloudEvent, SpecVersion};
+    ///
+    /// let event = CloudEvent::V0_2(cloudevent_v0_2!(
+    ///     event_type: "test type",
+    ///     source: "http://www.google.com",
+    ///     event_id: "id",
+    ///     schemaurl: "http://www.google.com/schema.json",
+    /// ).unwrap());
+    ///
+    /// match event.to_version(SpecVersion::V1_0).unwrap() {
+    ///     CloudEvent::V1_0(e) => assert_eq!(e.dataschema(), Some("http://www.google.com/schema.json")),
+    ///     _ => unreachable!(),
+    /// }
+    /// ```
+    pub fn to_version(&self, version: SpecVersion) -> Result<CloudEvent, Error> {
+        let event = self.clone();
+        Ok(match version {
+            SpecVersion::V0_2 => CloudEvent::V0_2(CloudEventV0_2::try_from(event)?),
+            SpecVersion::V0_3 => CloudEvent::V0_3(CloudEventV0_3::try_from(event)?),
+            SpecVersion::V1_0 => CloudEvent::V1_0(CloudEventV1_0::try_from(event)?),
+        })
+    }
+}
+
+impl Validate for CloudEvent {
+    fn validate(&self) -> Result<(), Vec<Error>> {
+        match self {
+            CloudEvent::V1_0(event) => event.validate(),
+            CloudEvent::V0_3(event) => event.validate(),
+            CloudEvent::V0_2(event) => event.validate(),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for CloudEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let attributes = Attributes::deserialize(deserializer)?;
+        let spec_version = attributes.spec_version().map_err(|e| match e {
+            Error::MissingRequiredAttribute(name) => de::Error::missing_field(name),
+            e => de::Error::custom(e),
+        })?;
+        attributes.into_event(spec_version)
+    }
+}
+
+impl FromStr for CloudEvent {
+    type Err = Error;
+
+    /// Parse an event in the JSON format.
+    ///
+    /// Unlike deserializing with `serde_json`, the errors of the `specversion` attribute
+    /// are returned as is, e.g. [`Error::UnknownSpecVersion`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::{CloudEvent, Error};
+    ///
+    /// let result = r#"{"specversion":"0.1","type":"t","source":"/s","id":"1"}"#.parse::<CloudEvent>();
+    /// assert!(matches!(result, Err(Error::UnknownSpecVersion(ref v)) if v == "0.1"));
+    /// ```
+    ///
+    /// [`Error::UnknownSpecVersion`]: enum.Error.html#variant.UnknownSpecVersion
+    fn from_str(s: &str) -> Result<Self, Error> {
+        let attributes: Attributes = serde_json::from_str(s)?;
+        let spec_version = attributes.spec_version()?;
+        Ok(attributes.into_event::<serde_json::Error>(spec_version)?)
+    }
+}
+
+/// Attributes buffered until the spec version is known.
+struct Attributes(BTreeMap<String, Attribute>);
+
+impl Attributes {
+    fn spec_version(&self) -> Result<SpecVersion, Error> {
+        match self.0.get("specversion") {
+            Some(Attribute::Json(Value::String(version))) => version.parse(),
+            Some(_) => Err(Error::InvalidAttributeValue("specversion".to_owned())),
+            None => Err(Error::MissingRequiredAttribute("specversion")),
+        }
+    }
+
+    fn into_event<E: de::Error>(self, spec_version: SpecVersion) -> Result<CloudEvent, E> {
+        match spec_version {
+            SpecVersion::V0_2 => deserialize_version(self.0).map(CloudEvent::V0_2),
+            SpecVersion::V0_3 => deserialize_version(self.0).map(CloudEvent::V0_3),
+            SpecVersion::V1_0 => deserialize_version(self.0).map(CloudEvent::V1_0),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Attributes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(AttributesVisitor)
+    }
+}
+
+struct AttributesVisitor;
+
+impl<'de> Visitor<'de> for AttributesVisitor {
+    type Value = Attributes;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut attributes = BTreeMap::new();
+        while let Some(name) = map.next_key::<String>()? {
+            if attributes.contains_key(&name) {
+                return Err(duplicate_attribute(&name));
+            }
+            let value = map.next_value()?;
+            attributes.insert(name, value);
+        }
+        Ok(Attributes(attributes))
+    }
+}
+
+/// Error for a member read twice, reported with `duplicate_field` for the members defined
+/// by the spec versions.
+pub(crate) fn duplicate_attribute<E: de::Error>(name: &str) -> E {
+    let known = [SpecVersion::V0_2, SpecVersion::V0_3, SpecVersion::V1_0]
+        .iter()
+        .flat_map(|version| version.attribute_names())
+        .chain(DATA_MEMBERS)
+        .find(|known| **known == name);
+    match known {
+        Some(known) => E::duplicate_field(known),
+        None => E::custom(format_args!("duplicate field `{}`", name)),
+    }
+}
+
+/// Deserialize a spec version specific event, prefixing errors with the attribute
+/// that failed to deserialize.
+fn deserialize_version<T, E>(attributes: BTreeMap<String, Attribute>) -> Result<T, E>
+where
+    T: DeserializeOwned,
+    E: de::Error,
+{
+    let attributes = MapDeserializer::<_, serde_json::Error>::new(attributes.into_iter());
+    serde_path_to_error::deserialize(attributes).map_err(|e| {
+        if e.path().iter().next().is_some() {
+            E::custom(format_args!(
+                "invalid attribute `{}`: {}",
+                e.path(),
+                e.inner()
+            ))
+        } else {
+            E::custom(e.inner())
+        }
+    })
+}
+
+/// An attribute buffered until the spec version is known.
+///
+/// Byte strings are kept apart, as JSON values cannot hold them.
+#[derive(Clone)]
+enum Attribute {
+    Json(Value),
+    Binary(Vec<u8>),
+}
+
+impl<'de> Deserialize<'de> for Attribute {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(AttributeVisitor)
+    }
+}
+
+struct AttributeVisitor;
+
+impl<'de> Visitor<'de> for AttributeVisitor {
+    type Value = Attribute;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an attribute value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::Bool(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::from(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::from(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::from(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::from(v)))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::from(v)))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(Attribute::Binary(v.to_vec()))
+    }
+
+    fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(Attribute::Binary(v))
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::Null))
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E> {
+        Ok(Attribute::Json(Value::Null))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        Value::deserialize(SeqAccessDeserializer::new(seq)).map(Attribute::Json)
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        Value::deserialize(MapAccessDeserializer::new(map)).map(Attribute::Json)
+    }
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for Attribute {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for Attribute {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Attribute::Json(value) => value.deserialize_any(visitor),
+            Attribute::Binary(bytes) => visitor.visit_byte_buf(bytes),
+        }
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self {
+            Attribute::Json(value) => value.deserialize_option(visitor),
+            Attribute::Binary(bytes) => visitor.visit_some(Attribute::Binary(bytes)),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct map struct enum identifier ignored_any
+    }
+}
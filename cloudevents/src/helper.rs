@@ -28,6 +28,7 @@ macro_rules! get_event_field {
     ($event:expr, $value:ident) => {
         match $event {
             $crate::CloudEvent::V0_2(ref e) => e.$value(),
+            $crate::CloudEvent::V0_3(ref e) => e.$value(),
             $crate::CloudEvent::V1_0(ref e) => e.$value(),
         }
     };
@@ -0,0 +1,133 @@
+use crate::message::{data_from_bytes, data_to_bytes, EventDeserializer, EventSerializer};
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use http::header::{HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, CONTROLS};
+
+/// Prefix of the headers carrying context attributes and extensions.
+const PREFIX: &str = "ce-";
+
+/// Characters percent-encoded in header values, in addition to all non-ASCII characters.
+const HEADER_VALUE: &AsciiSet = &CONTROLS.add(b' ').add(b'"').add(b'%');
+
+/// Encode an event into the headers and the body of a binary mode message.
+pub(crate) fn encode(event: &CloudEvent, headers: &mut HeaderMap) -> Result<Vec<u8>, Error> {
+    event.clone().deserialize_event(BinaryWriter {
+        headers,
+        content_type: None,
+    })
+}
+
+/// Decode an event from the headers and the body of a binary mode message.
+pub(crate) fn decode(headers: &HeaderMap, body: &[u8]) -> Result<CloudEvent, Error> {
+    BinaryReader { headers, body }.into_event()
+}
+
+/// Writes the attributes and extensions to headers, and returns the data as body.
+struct BinaryWriter<'a> {
+    headers: &'a mut HeaderMap,
+    content_type: Option<&'static str>,
+}
+
+impl<'a> BinaryWriter<'a> {
+    fn insert(&mut self, name: &str, value: &str) -> Result<(), Error> {
+        let header = HeaderName::from_bytes(format!("{}{}", PREFIX, name).as_bytes())
+            .map_err(http::Error::from)?;
+        self.headers.insert(header, header_value(value)?);
+        Ok(())
+    }
+}
+
+impl<'a> EventSerializer<Vec<u8>> for BinaryWriter<'a> {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.content_type = Some(version.content_type_attribute());
+        self.insert("specversion", version.as_str())?;
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        if self.content_type == Some(name) {
+            self.headers.insert(CONTENT_TYPE, header_value(&value)?);
+        } else {
+            self.insert(name, &value)?;
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        self.insert(name, &value.to_string())?;
+        Ok(self)
+    }
+
+    fn end_with_data(self, data: Data) -> Result<Vec<u8>, Error> {
+        data_to_bytes(data)
+    }
+
+    fn end(self) -> Result<Vec<u8>, Error> {
+        Ok(Vec::new())
+    }
+}
+
+/// Reads the attributes and extensions from headers, and the data from the body.
+struct BinaryReader<'a> {
+    headers: &'a HeaderMap,
+    body: &'a [u8],
+}
+
+impl<'a> EventDeserializer for BinaryReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let spec_version: SpecVersion = match self.headers.get("ce-specversion") {
+            Some(value) => decode_header("ce-specversion", value)?.parse()?,
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let attributes = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        for (name, value) in self.headers {
+            if !name.as_str().starts_with(PREFIX) {
+                continue;
+            }
+            let attribute = &name.as_str()[PREFIX.len()..];
+            let value = decode_header(name.as_str(), value)?;
+            serializer = match attribute {
+                "specversion" => serializer,
+                a if attributes.contains(&a) => serializer.set_attribute(a, value)?,
+                a => serializer.set_extension(a, ExtensionValue::String(value))?,
+            };
+        }
+
+        let content_type = match self.headers.get(CONTENT_TYPE) {
+            Some(value) => Some(
+                value
+                    .to_str()
+                    .map_err(|_| Error::InvalidHeaderValue(CONTENT_TYPE.to_string()))?
+                    .to_owned(),
+            ),
+            None => None,
+        };
+        if let Some(content_type) = &content_type {
+            serializer = serializer
+                .set_attribute(spec_version.content_type_attribute(), content_type.clone())?;
+        }
+
+        if self.body.is_empty() {
+            serializer.end()
+        } else {
+            let data = data_from_bytes(content_type.as_deref(), self.body.to_vec())?;
+            serializer.end_with_data(data)
+        }
+    }
+}
+
+fn header_value(value: &str) -> Result<HeaderValue, Error> {
+    let encoded = utf8_percent_encode(value, HEADER_VALUE).to_string();
+    Ok(HeaderValue::from_str(&encoded).map_err(http::Error::from)?)
+}
+
+fn decode_header(name: &str, value: &HeaderValue) -> Result<String, Error> {
+    value
+        .to_str()
+        .ok()
+        .and_then(|v| percent_decode_str(v).decode_utf8().ok())
+        .map(|v| v.into_owned())
+        .ok_or_else(|| Error::InvalidHeaderValue(name.to_owned()))
+}
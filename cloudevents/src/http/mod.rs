@@ -0,0 +1,237 @@
+/*!
+HTTP protocol binding, available with the `http` feature.
+
+Implements the HTTP protocol binding (see [v1.0 HTTP Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/http-protocol-binding.md))
+on top of the [`http`](https://docs.rs/http) crate, in all three content modes:
+
+* **Binary**: the context attributes and extensions are mapped to percent-encoded `ce-` prefixed headers,
+  the content type attribute is mapped to the `Content-Type` header and the body carries the raw data.
+* **Structured**: the body carries the event in the JSON format, with the `application/cloudevents+json` content type.
+* **Batch**: the body carries many events in the JSON format, with the `application/cloudevents-batch+json` content type.
+
+When reading a message, the content mode is detected from its `Content-Type` header.
+
+# Example
+
+```
+use cloudevents::cloudevent;
+use cloudevents::{CloudEvent, Data};
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    datacontenttype: "text/plain",
+    data: Data::from_string("hello world"),
+).unwrap().into();
+
+let request = cloudevents::http::to_binary_request(http::Request::post("http://localhost"), &event).unwrap();
+assert_eq!(request.headers()["ce-id"], "id");
+assert_eq!(request.headers()["content-type"], "text/plain");
+assert_eq!(request.body(), b"hello world");
+
+let decoded = cloudevents::http::from_request(&request).unwrap();
+assert_eq!(decoded, event);
+
+let request = cloudevents::http::to_structured_request(http::Request::post("http://localhost"), &event).unwrap();
+assert_eq!(request.headers()["content-type"], "application/cloudevents+json; charset=UTF-8");
+
+let decoded = cloudevents::http::from_request(&request).unwrap();
+assert_eq!(decoded, event);
+```
+*/
+mod binary;
+mod structured;
+
+pub use crate::message::ContentMode;
+
+use crate::{CloudEvent, Error};
+use http::header::{HeaderMap, CONTENT_TYPE};
+use http::{request, response, Request, Response};
+
+/// Detect the content mode of a message from its `Content-Type` header.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::http::{content_mode, ContentMode};
+/// use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+///
+/// let mut headers = HeaderMap::new();
+/// assert_eq!(content_mode(&headers), ContentMode::Binary);
+///
+/// headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/cloudevents-batch+json"));
+/// assert_eq!(content_mode(&headers), ContentMode::Batch);
+/// ```
+pub fn content_mode(headers: &HeaderMap) -> ContentMode {
+    ContentMode::from_content_type(headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok()))
+}
+
+/// Create a binary mode HTTP request carrying the event.
+///
+/// The method, uri and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if the event cannot be mapped to headers.
+pub fn to_binary_request(
+    builder: request::Builder,
+    event: &CloudEvent,
+) -> Result<Request<Vec<u8>>, Error> {
+    build_request(builder, |headers| binary::encode(event, headers))
+}
+
+/// Create a binary mode HTTP response carrying the event.
+///
+/// The status and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if the event cannot be mapped to headers.
+pub fn to_binary_response(
+    builder: response::Builder,
+    event: &CloudEvent,
+) -> Result<Response<Vec<u8>>, Error> {
+    build_response(builder, |headers| binary::encode(event, headers))
+}
+
+/// Create a structured mode HTTP request carrying the event in the JSON format.
+///
+/// The method, uri and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if the event cannot be serialized.
+pub fn to_structured_request(
+    builder: request::Builder,
+    event: &CloudEvent,
+) -> Result<Request<Vec<u8>>, Error> {
+    build_request(builder, |headers| structured::encode(event, headers))
+}
+
+/// Create a structured mode HTTP response carrying the event in the JSON format.
+///
+/// The status and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if the event cannot be serialized.
+pub fn to_structured_response(
+    builder: response::Builder,
+    event: &CloudEvent,
+) -> Result<Response<Vec<u8>>, Error> {
+    build_response(builder, |headers| structured::encode(event, headers))
+}
+
+/// Create a batch mode HTTP request carrying the events in the JSON format.
+///
+/// The method, uri and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if an event cannot be serialized.
+pub fn to_batch_request(
+    builder: request::Builder,
+    events: &[CloudEvent],
+) -> Result<Request<Vec<u8>>, Error> {
+    build_request(builder, |headers| structured::encode_batch(events, headers))
+}
+
+/// Create a batch mode HTTP response carrying the events in the JSON format.
+///
+/// The status and any other header are taken from the `builder`.
+///
+/// # Errors
+///
+/// An error is returned if the `builder` is invalid, or if an event cannot be serialized.
+pub fn to_batch_response(
+    builder: response::Builder,
+    events: &[CloudEvent],
+) -> Result<Response<Vec<u8>>, Error> {
+    build_response(builder, |headers| structured::encode_batch(events, headers))
+}
+
+/// Read the event carried by a binary or structured mode HTTP request.
+///
+/// # Errors
+///
+/// An error is returned if the request is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_request<B: AsRef<[u8]>>(request: &Request<B>) -> Result<CloudEvent, Error> {
+    decode(request.headers(), request.body().as_ref())
+}
+
+/// Read the event carried by a binary or structured mode HTTP response.
+///
+/// # Errors
+///
+/// An error is returned if the response is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_response<B: AsRef<[u8]>>(response: &Response<B>) -> Result<CloudEvent, Error> {
+    decode(response.headers(), response.body().as_ref())
+}
+
+/// Read the events carried by an HTTP request in any content mode.
+///
+/// Binary and structured mode requests result in a single event.
+///
+/// # Errors
+///
+/// An error is returned if the event format is not supported, or if the request
+/// does not carry valid events.
+pub fn batch_from_request<B: AsRef<[u8]>>(request: &Request<B>) -> Result<Vec<CloudEvent>, Error> {
+    decode_batch(request.headers(), request.body().as_ref())
+}
+
+/// Read the events carried by an HTTP response in any content mode.
+///
+/// Binary and structured mode responses result in a single event.
+///
+/// # Errors
+///
+/// An error is returned if the event format is not supported, or if the response
+/// does not carry valid events.
+pub fn batch_from_response<B: AsRef<[u8]>>(
+    response: &Response<B>,
+) -> Result<Vec<CloudEvent>, Error> {
+    decode_batch(response.headers(), response.body().as_ref())
+}
+
+fn decode(headers: &HeaderMap, body: &[u8]) -> Result<CloudEvent, Error> {
+    match content_mode(headers) {
+        ContentMode::Binary => binary::decode(headers, body),
+        ContentMode::Structured => structured::decode(headers, body),
+        ContentMode::Batch => Err(Error::UnsupportedContentType(
+            crate::message::CLOUDEVENTS_BATCH_JSON.to_owned(),
+        )),
+    }
+}
+
+fn decode_batch(headers: &HeaderMap, body: &[u8]) -> Result<Vec<CloudEvent>, Error> {
+    match content_mode(headers) {
+        ContentMode::Batch => structured::decode_batch(headers, body),
+        _ => decode(headers, body).map(|event| vec![event]),
+    }
+}
+
+fn build_request<F>(builder: request::Builder, encode: F) -> Result<Request<Vec<u8>>, Error>
+where
+    F: FnOnce(&mut HeaderMap) -> Result<Vec<u8>, Error>,
+{
+    let mut headers = HeaderMap::new();
+    let body = encode(&mut headers)?;
+    let mut request = builder.body(body)?;
+    request.headers_mut().extend(headers);
+    Ok(request)
+}
+
+fn build_response<F>(builder: response::Builder, encode: F) -> Result<Response<Vec<u8>>, Error>
+where
+    F: FnOnce(&mut HeaderMap) -> Result<Vec<u8>, Error>,
+{
+    let mut headers = HeaderMap::new();
+    let body = encode(&mut headers)?;
+    let mut response = builder.body(body)?;
+    response.headers_mut().extend(headers);
+    Ok(response)
+}
@@ -0,0 +1,39 @@
+use crate::message::{ensure_json_format, CLOUDEVENTS_BATCH_JSON, CLOUDEVENTS_JSON};
+use crate::{Batch, CloudEvent, Error};
+use http::header::{HeaderMap, HeaderValue, CONTENT_TYPE};
+
+/// Encode an event into the headers and the body of a structured mode message.
+pub(crate) fn encode(event: &CloudEvent, headers: &mut HeaderMap) -> Result<Vec<u8>, Error> {
+    headers.insert(CONTENT_TYPE, json_content_type(CLOUDEVENTS_JSON));
+    Ok(serde_json::to_vec(event)?)
+}
+
+/// Encode events into the headers and the body of a batch mode message.
+pub(crate) fn encode_batch(
+    events: &[CloudEvent],
+    headers: &mut HeaderMap,
+) -> Result<Vec<u8>, Error> {
+    headers.insert(CONTENT_TYPE, json_content_type(CLOUDEVENTS_BATCH_JSON));
+    Ok(serde_json::to_vec(events)?)
+}
+
+/// Decode an event from the body of a structured mode message.
+pub(crate) fn decode(headers: &HeaderMap, body: &[u8]) -> Result<CloudEvent, Error> {
+    ensure_json_format(content_type(headers), CLOUDEVENTS_JSON)?;
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// Decode events from the body of a batch mode message.
+pub(crate) fn decode_batch(headers: &HeaderMap, body: &[u8]) -> Result<Vec<CloudEvent>, Error> {
+    ensure_json_format(content_type(headers), CLOUDEVENTS_BATCH_JSON)?;
+    Ok(Batch::from_slice(body)?.into_events())
+}
+
+fn json_content_type(media_type: &'static str) -> HeaderValue {
+    HeaderValue::from_str(&format!("{}; charset=UTF-8", media_type))
+        .expect("content types are valid header values")
+}
+
+fn content_type(headers: &HeaderMap) -> Option<&str> {
+    headers.get(CONTENT_TYPE).and_then(|v| v.to_str().ok())
+}
@@ -0,0 +1,61 @@
+use super::KafkaRecord;
+use rdkafka::message::{Headers, Message, OwnedHeaders};
+use rdkafka::producer::BaseRecord;
+
+impl KafkaRecord {
+    /// Create a record from a message consumed with `rdkafka`.
+    ///
+    /// Headers without a value are read as empty.
+    pub fn from_message<M: Message>(message: &M) -> Self {
+        let headers = match message.headers() {
+            Some(headers) => (0..headers.count())
+                .filter_map(|idx| headers.get(idx))
+                .map(|(name, value)| (name.to_owned(), value.to_vec()))
+                .collect(),
+            None => Vec::new(),
+        };
+        KafkaRecord {
+            key: message.key().map(<[u8]>::to_vec),
+            headers,
+            value: message.payload().map(<[u8]>::to_vec),
+        }
+    }
+
+    /// Get the headers of the record, to produce it with `rdkafka`.
+    pub fn owned_headers(&self) -> OwnedHeaders {
+        self.headers.iter().fold(
+            OwnedHeaders::new_with_capacity(self.headers.len()),
+            |h, (name, value)| h.add(name, value.as_slice()),
+        )
+    }
+
+    /// Create a record to produce to the `topic` with an `rdkafka` producer.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::cloudevent;
+    /// use cloudevents::kafka::to_binary_record;
+    /// use cloudevents::CloudEvent;
+    ///
+    /// let event: CloudEvent = cloudevent!(
+    ///     event_type: "test type",
+    ///     source: "http://www.google.com",
+    ///     event_id: "id",
+    /// ).unwrap().into();
+    ///
+    /// let record = to_binary_record(&event).unwrap();
+    /// let base_record = record.to_base_record("events");
+    /// assert_eq!(base_record.topic, "events");
+    /// ```
+    pub fn to_base_record<'a>(&'a self, topic: &'a str) -> BaseRecord<'a, [u8], [u8]> {
+        let mut record = BaseRecord::to(topic).headers(self.owned_headers());
+        if let Some(key) = &self.key {
+            record = record.key(key.as_slice());
+        }
+        if let Some(value) = &self.value {
+            record = record.payload(value.as_slice());
+        }
+        record
+    }
+}
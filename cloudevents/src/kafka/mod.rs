@@ -0,0 +1,253 @@
+/*!
+Kafka protocol binding.
+
+Implements the Kafka protocol binding (see [v1.0 Kafka Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/kafka-protocol-binding.md))
+on top of [`KafkaRecord`], a client independent view of a Kafka record, in two content modes:
+
+* **Binary**: the context attributes and extensions are mapped to `ce_` prefixed headers,
+  the content type attribute is mapped to the `content-type` header and the value carries the raw data.
+* **Structured**: the value carries the event in the JSON format, with the `application/cloudevents+json`
+  content type.
+
+In both modes, the `partitionkey` extension is mapped to the key of the record.
+When reading a record, the content mode is detected from its `content-type` header.
+
+Enable the `rdkafka` feature to convert records from and to the message types of the
+[`rdkafka`](https://docs.rs/rdkafka) crate.
+
+# Example
+
+```
+use cloudevents::cloudevent;
+use cloudevents::kafka::{from_record, to_binary_record};
+use cloudevents::{CloudEvent, Data, ExtensionValue};
+use std::collections::HashMap;
+
+let mut extensions = HashMap::new();
+extensions.insert("partitionkey".to_owned(), ExtensionValue::from("user-42"));
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    datacontenttype: "text/plain",
+    data: Data::from_string("hello world"),
+    extensions: extensions,
+).unwrap().into();
+
+let record = to_binary_record(&event).unwrap();
+assert_eq!(record.header("ce_id"), Some(&b"id"[..]));
+assert_eq!(record.header("content-type"), Some(&b"text/plain"[..]));
+assert_eq!(record.key.as_deref(), Some(&b"user-42"[..]));
+assert_eq!(record.value.as_deref(), Some(&b"hello world"[..]));
+
+assert_eq!(from_record(&record).unwrap(), event);
+```
+
+[`KafkaRecord`]: struct.KafkaRecord.html
+*/
+#[cfg(feature = "rdkafka")]
+mod adapter;
+
+use crate::message::{
+    data_from_bytes, data_to_bytes, ensure_json_format, ContentMode, EventDeserializer,
+    EventSerializer, CLOUDEVENTS_BATCH_JSON, CLOUDEVENTS_JSON,
+};
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+
+/// Prefix of the headers carrying context attributes and extensions.
+const PREFIX: &str = "ce_";
+
+/// Header carrying the content type.
+const CONTENT_TYPE: &str = "content-type";
+
+/// Extension mapped to the key of the record.
+const PARTITION_KEY: &str = "partitionkey";
+
+/// A Kafka record, independent of any client library.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct KafkaRecord {
+    /// The key of the record.
+    pub key: Option<Vec<u8>>,
+    /// The headers of the record, in order.
+    pub headers: Vec<(String, Vec<u8>)>,
+    /// The value of the record.
+    pub value: Option<Vec<u8>>,
+}
+
+impl KafkaRecord {
+    /// Get the value of a header.
+    ///
+    /// Kafka allows repeated headers, in which case the last one is returned.
+    pub fn header(&self, name: &str) -> Option<&[u8]> {
+        self.headers
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_slice())
+    }
+
+    fn content_type(&self) -> Result<Option<&str>, Error> {
+        self.header(CONTENT_TYPE)
+            .map(|value| header_str(CONTENT_TYPE, value))
+            .transpose()
+    }
+}
+
+/// Create a binary mode record carrying the event.
+///
+/// # Errors
+///
+/// An error is returned if the data of the event cannot be serialized.
+pub fn to_binary_record(event: &CloudEvent) -> Result<KafkaRecord, Error> {
+    event.clone().deserialize_event(BinaryWriter {
+        record: KafkaRecord::default(),
+        content_type: None,
+    })
+}
+
+/// Create a structured mode record carrying the event in the JSON format.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_structured_record(event: &CloudEvent) -> Result<KafkaRecord, Error> {
+    Ok(KafkaRecord {
+        key: partition_key(event),
+        headers: vec![(
+            CONTENT_TYPE.to_owned(),
+            CLOUDEVENTS_JSON.as_bytes().to_vec(),
+        )],
+        value: Some(serde_json::to_vec(event)?),
+    })
+}
+
+/// Read the event carried by a binary or structured mode record.
+///
+/// # Errors
+///
+/// An error is returned if the record is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_record(record: &KafkaRecord) -> Result<CloudEvent, Error> {
+    let content_type = record.content_type()?;
+    match ContentMode::from_content_type(content_type) {
+        ContentMode::Binary => BinaryReader(record).into_event(),
+        ContentMode::Structured => {
+            ensure_json_format(content_type, CLOUDEVENTS_JSON)?;
+            Ok(serde_json::from_slice(
+                record.value.as_deref().unwrap_or_default(),
+            )?)
+        }
+        ContentMode::Batch => Err(Error::UnsupportedContentType(
+            CLOUDEVENTS_BATCH_JSON.to_owned(),
+        )),
+    }
+}
+
+fn partition_key(event: &CloudEvent) -> Option<Vec<u8>> {
+    let extensions = match event {
+        CloudEvent::V0_2(e) => e.extensions(),
+        CloudEvent::V0_3(e) => e.extensions(),
+        CloudEvent::V1_0(e) => e.extensions(),
+    };
+    extensions?
+        .get(PARTITION_KEY)
+        .map(|value| value.to_string().into_bytes())
+}
+
+/// Writes the attributes and extensions to headers, and the data to the value.
+struct BinaryWriter {
+    record: KafkaRecord,
+    content_type: Option<&'static str>,
+}
+
+impl BinaryWriter {
+    fn push(&mut self, name: String, value: String) {
+        self.record.headers.push((name, value.into_bytes()));
+    }
+}
+
+impl EventSerializer<KafkaRecord> for BinaryWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.content_type = Some(version.content_type_attribute());
+        self.push(
+            format!("{}specversion", PREFIX),
+            version.as_str().to_owned(),
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        if self.content_type == Some(name) {
+            self.push(CONTENT_TYPE.to_owned(), value);
+        } else {
+            self.push(format!("{}{}", PREFIX, name), value);
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let value = value.to_string();
+        if name == PARTITION_KEY {
+            self.record.key = Some(value.clone().into_bytes());
+        }
+        self.push(format!("{}{}", PREFIX, name), value);
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<KafkaRecord, Error> {
+        self.record.value = Some(data_to_bytes(data)?);
+        Ok(self.record)
+    }
+
+    fn end(self) -> Result<KafkaRecord, Error> {
+        Ok(self.record)
+    }
+}
+
+/// Reads the attributes and extensions from headers, and the data from the value.
+struct BinaryReader<'a>(&'a KafkaRecord);
+
+impl<'a> EventDeserializer for BinaryReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let record = self.0;
+        let spec_version: SpecVersion = match record.header("ce_specversion") {
+            Some(value) => header_str("ce_specversion", value)?.parse()?,
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let attributes = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        for (name, value) in &record.headers {
+            if !name.starts_with(PREFIX) {
+                continue;
+            }
+            let attribute = &name[PREFIX.len()..];
+            let value = header_str(name, value)?.to_owned();
+            serializer = match attribute {
+                "specversion" => serializer,
+                a if attributes.contains(&a) => serializer.set_attribute(a, value)?,
+                a => serializer.set_extension(a, ExtensionValue::String(value))?,
+            };
+        }
+
+        let content_type = record.content_type()?;
+        if let Some(content_type) = content_type {
+            serializer = serializer.set_attribute(
+                spec_version.content_type_attribute(),
+                content_type.to_owned(),
+            )?;
+        }
+
+        match &record.value {
+            Some(value) if !value.is_empty() => {
+                serializer.end_with_data(data_from_bytes(content_type, value.clone())?)
+            }
+            _ => serializer.end(),
+        }
+    }
+}
+
+fn header_str<'a>(name: &str, value: &'a [u8]) -> Result<&'a str, Error> {
+    std::str::from_utf8(value).map_err(|_| Error::InvalidHeaderValue(name.to_owned()))
+}
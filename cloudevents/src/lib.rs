@@ -2,9 +2,10 @@
 Implementation of the core in version:
 
 * **v1.0** (see [v1.0 CloudEvents specification](https://github.com/cloudevents/spec/blob/v1.0/spec.md) and [v1.0 JSON Event Format](https://github.com/cloudevents/spec/blob/v1.0/json-format.md))
+* **v0.3** (see [v0.3 CloudEvents specification](https://github.com/cloudevents/spec/blob/v0.3/spec.md) and [v0.3 JSON Event Format](https://github.com/cloudevents/spec/blob/v0.3/json-format.md))
 * **v0.2** (see [v0.2 CloudEvents specification](https://github.com/cloudevents/spec/blob/v0.2/spec.md) and [v0.2 JSON Event Format](https://github.com/cloudevents/spec/blob/v0.2/json-format.md))
 
-This library is meant to provide the base for other CloudEvent transport bindings and formats. It only implements the core specification and the JSON format.
+This library is meant to provide the base for other CloudEvent transport bindings and formats. It implements the core specification, the JSON and Avro formats, the AMQP, Kafka, MQTT, NATS and WebSocket protocol bindings, and optionally the Protobuf and XML formats, the CBOR and MessagePack structured encodings and the HTTP protocol binding.
 
 # Create CloudEvent according to latest spec
 
@@ -16,7 +17,7 @@ A cloud event can be create in two different ways:
 use cloudevents::cloudevent;
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::CloudEvent;
-use failure::Error;
+use cloudevents::Error;
 
 let event: Result<CloudEvent, Error> = cloudevent!(
     event_type: "test type",
@@ -35,7 +36,7 @@ let event: Result<CloudEvent, Error> = cloudevent!(
 ```
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::CloudEventLatest;
-use failure::Error;
+use cloudevents::Error;
 
 let event: Result<CloudEventLatest, Error> = CloudEventBuilder::latest()
     .event_id("id")
@@ -95,14 +96,134 @@ match event {
 }
 ```
 
+## Validation
+
+Deserializing only checks the types of the attributes. Use [`Validate`] to check every other
+requirement of the spec, like non-empty attributes or valid extension names, or deserialize into
+[`Validated`] to do both at once.
+
+```
+use serde_json;
+use cloudevents::{CloudEvent, Validate, Validated};
+
+let data = "{\"type\":\"test type\",\"specversion\":\"1.0\",\"source\":\"http://www.google.com\",\"id\":\"\"}";
+
+let event: CloudEvent = serde_json::from_str(data).unwrap();
+assert!(event.validate().is_err());
+assert!(serde_json::from_str::<Validated<CloudEvent>>(data).is_err());
+```
+
+## Batch
+
+A [`Batch`] of events, possibly using different spec versions, is serialized as a JSON array
+according to the JSON batch format. Use [`Batch::reader`](struct.Batch.html#method.reader) to parse huge batches event by event.
+
+```
+use cloudevents::Batch;
+
+let data = "[{\"type\":\"test type\",\"specversion\":\"1.0\",\"source\":\"http://www.google.com\",\"id\":\"1\"},{\"type\":\"test type\",\"specversion\":\"0.2\",\"source\":\"http://www.google.com\",\"id\":\"2\"}]";
+
+let batch = Batch::from_slice(data.as_bytes()).unwrap();
+assert_eq!(batch.len(), 2);
+```
+
+## Borrowed deserialization
+
+A [`CloudEventRef`](v1_0/struct.CloudEventRef.html) borrows its attributes and raw data from the JSON it is deserialized from, which avoids
+allocating them when only a few attributes of each event are read, e.g. to route events. It can be
+converted into a [`CloudEventV1_0`](v1_0/struct.CloudEventV1_0.html) with `TryFrom`.
+
+```
+use cloudevents::v1_0::CloudEventRef;
+
+let data = "{\"type\":\"test type\",\"specversion\":\"1.0\",\"source\":\"http://www.google.com\",\"id\":\"id\"}";
+
+let event: CloudEventRef = serde_json::from_str(data).unwrap();
+assert_eq!(event.event_type(), "test type");
+```
+
+# Spec version conversion
+
+A [`CloudEvent`] can be converted between spec versions with [`CloudEvent::to_version`],
+or with the `From`/`TryFrom` implementations between the version specific types.
+Converting fails if the event populates an attribute which has no counterpart in the target version.
+
+```
+use cloudevents::{cloudevent_v0_2, CloudEvent, SpecVersion};
+
+let event = CloudEvent::V0_2(cloudevent_v0_2!(
+  event_type: "test type",
+  source: "http://www.google.com",
+  event_id: "id",
+  contenttype: "application/json",
+).unwrap());
+
+let event = event.to_version(SpecVersion::V1_0).unwrap();
+assert_eq!(event.spec_version(), SpecVersion::V1_0);
+```
+
+# Avro format
+
+Events of spec version 1.0 can be serialized in the Avro format, see the [`avro`](avro/index.html) module.
+The canonical schema is shipped in `cloudevents/avro/cloudevents.avsc`.
+
+# Protobuf format
+
+Enable the `protobuf` feature to serialize events of spec version 1.0 in the Protobuf format,
+including its batch format, see the [`protobuf`](protobuf/index.html) module.
+
+# XML format
+
+Enable the `xml` feature to serialize events of spec version 1.0 in the XML format, see the [`xml`](xml/index.html) module.
+
+# CBOR and MessagePack encodings
+
+Enable the `cbor` or `msgpack` feature to serialize events in CBOR or MessagePack, with the model
+of the JSON format and binary data as native byte strings, see the [`cbor`](cbor/index.html) and [`msgpack`](msgpack/index.html) modules.
+
+# HTTP protocol binding
+
+Enable the `http` feature to send and receive events in binary, structured or batch content mode with the
+[`http`](https://docs.rs/http) crate, see the [`http`](http/index.html) module.
+
+# AMQP protocol binding
+
+The [`amqp`](amqp/index.html) module maps events to and from a client independent AMQP 1.0 message, in binary
+or structured content mode. Messages can be encoded to and decoded from the AMQP 1.0 format of their sections.
+
+# Kafka protocol binding
+
+The [`kafka`](kafka/index.html) module maps events to and from a client independent Kafka record, in binary
+or structured content mode. Enable the `rdkafka` feature to convert records from and to the message types of the
+[`rdkafka`](https://docs.rs/rdkafka) crate.
+
+# MQTT protocol binding
+
+The [`mqtt`](mqtt/index.html) module maps events to and from a client independent MQTT PUBLISH packet, in binary
+or structured content mode with MQTT 5, and in structured content mode with MQTT 3.1.1. Enable the `rumqttc` feature
+to convert packets from and to the PUBLISH packets of the [`rumqttc`](https://docs.rs/rumqttc) crate.
+
+# NATS protocol binding
+
+The [`nats`](nats/index.html) module maps events to and from a client independent NATS message, in binary
+or structured content mode.
+
+# WebSocket protocol binding
+
+The [`websocket`](websocket/index.html) module frames events as WebSocket messages with the `cloudevents.json`
+subprotocol, and negotiates the subprotocol from a `Sec-WebSocket-Protocol` header.
+
+Other protocol bindings can be written on top of the [`message`](message/index.html) module, which converts
+between events and messages without going through an event format.
+
 # License
 
 Licensed under either of
 
 * Apache License, Version 2.0
-   ([LICENSE-APACHE](LICENSE-APACHE) or http://www.apache.org/licenses/LICENSE-2.0)
+  ([LICENSE-APACHE](LICENSE-APACHE) or http://www.apache.org/licenses/LICENSE-2.0)
 * MIT license
-   ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
+  ([LICENSE-MIT](LICENSE-MIT) or http://opensource.org/licenses/MIT)
 
 at your option.
 
@@ -113,16 +234,41 @@ for inclusion in the work by you, as defined in the Apache-2.0 license, shall be
 dual licensed as above, without any additional terms or conditions.
 */
 
+pub mod amqp;
+pub mod avro;
+mod batch;
 mod builder;
+#[cfg(feature = "cbor")]
+pub mod cbor;
 mod common;
+mod convert;
+mod error;
 mod event;
 mod helper;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod kafka;
+pub mod message;
+pub mod mqtt;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod nats;
+#[cfg(feature = "protobuf")]
+pub mod protobuf;
 pub mod v0_2;
+pub mod v0_3;
 pub mod v1_0;
+mod validation;
+pub mod websocket;
+#[cfg(feature = "xml")]
+pub mod xml;
 
+pub use crate::batch::{Batch, BatchReader};
 pub use crate::builder::CloudEventBuilder;
-pub use crate::common::{Data, ExtensionValue};
+pub use crate::common::{Data, ExtensionValue, SpecVersion};
+pub use crate::error::Error;
 pub use crate::event::CloudEvent;
+pub use crate::validation::{Validate, Validated};
 
 use crate::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
 
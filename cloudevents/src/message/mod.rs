@@ -0,0 +1,340 @@
+/*!
+Protocol agnostic reading and writing of events.
+
+Protocol bindings convert between messages and events without going through an event format:
+
+* An [`EventSerializer`] receives the spec version, each attribute, each extension and the data
+  of an event, and produces a message or an event out of them. The version builders are
+  serializers producing events, and [`BuilderSink`] produces a [`CloudEvent`] in whatever spec
+  version it receives.
+* An [`EventDeserializer`] walks over the content of an event or a message and feeds it to an
+  [`EventSerializer`]. Events are deserializers, and so are messages read by protocol bindings.
+
+# Example
+
+```
+use cloudevents::message::{EventDeserializer, EventSerializer};
+use cloudevents::{cloudevent, CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+
+/// Collects the attributes of an event as `name=value` lines.
+#[derive(Default)]
+struct Lines(Vec<String>);
+
+impl EventSerializer<Vec<String>> for Lines {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.0.push(format!("specversion={}", version));
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        self.0.push(format!("{}={}", name, value));
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        self.0.push(format!("{}={}", name, value));
+        Ok(self)
+    }
+
+    fn end_with_data(self, _data: Data) -> Result<Vec<String>, Error> {
+        Ok(self.0)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.0)
+    }
+}
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+).unwrap().into();
+
+let lines = event.clone().deserialize_event(Lines::default()).unwrap();
+assert_eq!(lines, vec!["specversion=1.0", "type=test type", "source=http://www.google.com", "id=id"]);
+
+// Every event is a deserializer, so it can be read back into an event
+assert_eq!(event.clone().into_event().unwrap(), event);
+```
+
+[`EventSerializer`]: trait.EventSerializer.html
+[`EventDeserializer`]: trait.EventDeserializer.html
+[`BuilderSink`]: struct.BuilderSink.html
+[`CloudEvent`]: ../enum.CloudEvent.html
+*/
+use crate::v0_2::CloudEventV0_2Builder;
+use crate::v0_3::CloudEventV0_3Builder;
+use crate::v1_0::CloudEventV1_0Builder;
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use chrono::prelude::{DateTime, FixedOffset, SecondsFormat};
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Receives the content of an event, to produce a message or an event out of it.
+///
+/// [`set_spec_version`] is called first, then [`set_attribute`] and [`set_extension`] for every
+/// populated attribute and extension, and finally either [`end_with_data`] or [`end`].
+///
+/// [`set_spec_version`]: #tymethod.set_spec_version
+/// [`set_attribute`]: #tymethod.set_attribute
+/// [`set_extension`]: #tymethod.set_extension
+/// [`end_with_data`]: #tymethod.end_with_data
+/// [`end`]: #tymethod.end
+pub trait EventSerializer<R>: Sized {
+    /// Receive the spec version of the event.
+    fn set_spec_version(self, version: SpecVersion) -> Result<Self, Error>;
+
+    /// Receive a context attribute, other than `specversion`, in its string representation.
+    fn set_attribute(self, name: &str, value: String) -> Result<Self, Error>;
+
+    /// Receive an extension.
+    fn set_extension(self, name: &str, value: ExtensionValue) -> Result<Self, Error>;
+
+    /// Receive the data, completing the event.
+    fn end_with_data(self, data: Data) -> Result<R, Error>;
+
+    /// Complete an event without data.
+    fn end(self) -> Result<R, Error>;
+}
+
+/// Walks over the content of an event, feeding it to an [`EventSerializer`].
+///
+/// [`EventSerializer`]: trait.EventSerializer.html
+pub trait EventDeserializer: Sized {
+    /// Feed the content of the event to the `serializer`.
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error>;
+
+    /// Build a [`CloudEvent`] with the version builders.
+    ///
+    /// [`CloudEvent`]: ../enum.CloudEvent.html
+    fn into_event(self) -> Result<CloudEvent, Error> {
+        self.deserialize_event(BuilderSink::default())
+    }
+}
+
+/// Serializer building a [`CloudEvent`] with the builder of the spec version it receives.
+///
+/// [`CloudEvent`]: ../enum.CloudEvent.html
+#[derive(Debug, Default)]
+pub struct BuilderSink(Option<VersionBuilder>);
+
+#[derive(Debug)]
+enum VersionBuilder {
+    V0_2(CloudEventV0_2Builder),
+    V0_3(CloudEventV0_3Builder),
+    V1_0(CloudEventV1_0Builder),
+}
+
+impl BuilderSink {
+    fn builder(self) -> Result<VersionBuilder, Error> {
+        self.0.ok_or(Error::MissingRequiredAttribute("specversion"))
+    }
+}
+
+impl EventSerializer<CloudEvent> for BuilderSink {
+    fn set_spec_version(self, version: SpecVersion) -> Result<Self, Error> {
+        Ok(BuilderSink(Some(match version {
+            SpecVersion::V0_2 => VersionBuilder::V0_2(CloudEventV0_2Builder::default()),
+            SpecVersion::V0_3 => VersionBuilder::V0_3(CloudEventV0_3Builder::default()),
+            SpecVersion::V1_0 => VersionBuilder::V1_0(CloudEventV1_0Builder::default()),
+        })))
+    }
+
+    fn set_attribute(self, name: &str, value: String) -> Result<Self, Error> {
+        Ok(BuilderSink(Some(match self.builder()? {
+            VersionBuilder::V0_2(b) => VersionBuilder::V0_2(b.set_attribute(name, value)?),
+            VersionBuilder::V0_3(b) => VersionBuilder::V0_3(b.set_attribute(name, value)?),
+            VersionBuilder::V1_0(b) => VersionBuilder::V1_0(b.set_attribute(name, value)?),
+        })))
+    }
+
+    fn set_extension(self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        Ok(BuilderSink(Some(match self.builder()? {
+            VersionBuilder::V0_2(b) => VersionBuilder::V0_2(b.set_extension(name, value)?),
+            VersionBuilder::V0_3(b) => VersionBuilder::V0_3(b.set_extension(name, value)?),
+            VersionBuilder::V1_0(b) => VersionBuilder::V1_0(b.set_extension(name, value)?),
+        })))
+    }
+
+    fn end_with_data(self, data: Data) -> Result<CloudEvent, Error> {
+        Ok(match self.builder()? {
+            VersionBuilder::V0_2(b) => CloudEvent::V0_2(b.end_with_data(data)?),
+            VersionBuilder::V0_3(b) => CloudEvent::V0_3(b.end_with_data(data)?),
+            VersionBuilder::V1_0(b) => CloudEvent::V1_0(b.end_with_data(data)?),
+        })
+    }
+
+    fn end(self) -> Result<CloudEvent, Error> {
+        Ok(match self.builder()? {
+            VersionBuilder::V0_2(b) => CloudEvent::V0_2(EventSerializer::end(b)?),
+            VersionBuilder::V0_3(b) => CloudEvent::V0_3(EventSerializer::end(b)?),
+            VersionBuilder::V1_0(b) => CloudEvent::V1_0(EventSerializer::end(b)?),
+        })
+    }
+}
+
+impl EventDeserializer for CloudEvent {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        match self {
+            CloudEvent::V0_2(event) => event.deserialize_event(serializer),
+            CloudEvent::V0_3(event) => event.deserialize_event(serializer),
+            CloudEvent::V1_0(event) => event.deserialize_event(serializer),
+        }
+    }
+}
+
+/// Content type of a structured mode message in the JSON format.
+pub const CLOUDEVENTS_JSON: &str = "application/cloudevents+json";
+
+/// Content type of a batch mode message in the JSON format.
+pub const CLOUDEVENTS_BATCH_JSON: &str = "application/cloudevents-batch+json";
+
+/// Content modes of the protocol bindings.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum ContentMode {
+    /// The attributes are carried by the message metadata and the data by the payload.
+    Binary,
+    /// The payload carries a single event in an event format.
+    Structured,
+    /// The payload carries many events in an event format.
+    Batch,
+}
+
+impl ContentMode {
+    /// Detect the content mode of a message from its content type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::message::ContentMode;
+    ///
+    /// assert_eq!(ContentMode::from_content_type(None), ContentMode::Binary);
+    /// assert_eq!(
+    ///     ContentMode::from_content_type(Some("application/cloudevents+json; charset=UTF-8")),
+    ///     ContentMode::Structured
+    /// );
+    /// ```
+    pub fn from_content_type(content_type: Option<&str>) -> Self {
+        let media_type = content_type.map(essence).unwrap_or_default();
+        if media_type.starts_with("application/cloudevents-batch") {
+            ContentMode::Batch
+        } else if media_type.starts_with("application/cloudevents") {
+            ContentMode::Structured
+        } else {
+            ContentMode::Binary
+        }
+    }
+}
+
+/// Ensure a structured or batch mode message uses the JSON format, the only event format supported.
+pub(crate) fn ensure_json_format(
+    content_type: Option<&str>,
+    media_type: &str,
+) -> Result<(), Error> {
+    let content_type = content_type.unwrap_or_default();
+    if essence(content_type) == media_type {
+        Ok(())
+    } else {
+        Err(Error::UnsupportedContentType(content_type.to_owned()))
+    }
+}
+
+/// Ensure a version builder receives the spec version it builds.
+pub(crate) fn expect_spec_version(expected: SpecVersion, found: SpecVersion) -> Result<(), Error> {
+    if expected == found {
+        Ok(())
+    } else {
+        Err(Error::SpecVersionMismatch { expected, found })
+    }
+}
+
+/// Get the raw bytes of the data, as carried by the body of a binary mode message.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::message::data_to_bytes;
+/// use cloudevents::Data;
+/// use serde_json::json;
+///
+/// let data = Data::from_serializable(json!({"key": "value"})).unwrap();
+/// assert_eq!(data_to_bytes(data).unwrap(), br#"{"key":"value"}"#);
+/// ```
+pub fn data_to_bytes(data: Data) -> Result<Vec<u8>, Error> {
+    Ok(match data {
+        Data::StringOrBinary(s) => s.into_bytes(),
+        Data::Binary(bytes) => bytes,
+        Data::Object(value) => serde_json::to_vec(&value)?,
+    })
+}
+
+/// Interpret the body of a binary mode message according to its content type.
+///
+/// JSON content is parsed as [`Data::Object`], text is kept as [`Data::StringOrBinary`] and
+/// everything else is [`Data::Binary`].
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::message::data_from_bytes;
+/// use cloudevents::Data;
+///
+/// let data = data_from_bytes(Some("text/plain; charset=utf-8"), b"hello".to_vec()).unwrap();
+/// assert_eq!(data, Data::from_string("hello"));
+///
+/// let data = data_from_bytes(None, b"hello".to_vec()).unwrap();
+/// assert_eq!(data, Data::from_binary(b"hello"));
+/// ```
+///
+/// [`Data::Object`]: ../enum.Data.html#variant.Object
+/// [`Data::StringOrBinary`]: ../enum.Data.html#variant.StringOrBinary
+/// [`Data::Binary`]: ../enum.Data.html#variant.Binary
+pub fn data_from_bytes(content_type: Option<&str>, bytes: Vec<u8>) -> Result<Data, Error> {
+    let media_type = content_type.map(essence);
+    match media_type.as_deref() {
+        Some(t) if is_json(t) => Ok(Data::Object(serde_json::from_slice::<Value>(&bytes)?)),
+        Some(t) if t.starts_with("text/") => match String::from_utf8(bytes) {
+            Ok(s) => Ok(Data::StringOrBinary(s)),
+            Err(e) => Ok(Data::Binary(e.into_bytes())),
+        },
+        _ => Ok(Data::Binary(bytes)),
+    }
+}
+
+/// Whether a media type, without parameters, denotes JSON content.
+pub(crate) fn is_json(media_type: &str) -> bool {
+    media_type == "application/json" || media_type.ends_with("+json")
+}
+
+/// The media type of a content type, without parameters.
+pub(crate) fn essence(content_type: &str) -> String {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase()
+}
+
+/// Feed the extensions in a stable order, then complete the event.
+pub(crate) fn end_with_extensions<R, S: EventSerializer<R>>(
+    mut serializer: S,
+    extensions: Option<HashMap<String, ExtensionValue>>,
+    data: Option<Data>,
+) -> Result<R, Error> {
+    let mut extensions: Vec<_> = extensions.into_iter().flatten().collect();
+    extensions.sort_by(|(a, _), (b, _)| a.cmp(b));
+    for (name, value) in extensions {
+        serializer = serializer.set_extension(&name, value)?;
+    }
+    match data {
+        Some(data) => serializer.end_with_data(data),
+        None => serializer.end(),
+    }
+}
+
+/// Format a timestamp like the JSON format does.
+pub(crate) fn format_time(time: &DateTime<FixedOffset>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
@@ -0,0 +1,60 @@
+use super::MqttPublish;
+use rumqttc::v5::mqttbytes::v5::{Publish as PublishV5, PublishProperties};
+
+impl MqttPublish {
+    /// Create a packet from an MQTT 3.1.1 PUBLISH packet received with `rumqttc`.
+    pub fn from_v3_1_1_publish(publish: &rumqttc::Publish) -> Self {
+        MqttPublish {
+            payload: publish.payload.to_vec(),
+            ..MqttPublish::default()
+        }
+    }
+
+    /// Create an MQTT 3.1.1 PUBLISH packet to send to the `topic` with `rumqttc`.
+    ///
+    /// The content type and user properties are dropped, as MQTT 3.1.1 does not support them.
+    pub fn to_v3_1_1_publish(&self, topic: &str, qos: rumqttc::QoS) -> rumqttc::Publish {
+        rumqttc::Publish::new(topic, qos, self.payload.clone())
+    }
+
+    /// Create a packet from an MQTT 5 PUBLISH packet received with `rumqttc`.
+    pub fn from_v5_publish(publish: &PublishV5) -> Self {
+        let properties = publish.properties.as_ref();
+        MqttPublish {
+            content_type: properties.and_then(|p| p.content_type.clone()),
+            user_properties: properties
+                .map(|p| p.user_properties.clone())
+                .unwrap_or_default(),
+            payload: publish.payload.to_vec(),
+        }
+    }
+
+    /// Create an MQTT 5 PUBLISH packet to send to the `topic` with `rumqttc`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::cloudevent;
+    /// use cloudevents::mqtt::{to_binary_publish, MqttPublish};
+    /// use cloudevents::CloudEvent;
+    /// use rumqttc::v5::mqttbytes::QoS;
+    ///
+    /// let event: CloudEvent = cloudevent!(
+    ///     event_type: "test type",
+    ///     source: "http://www.google.com",
+    ///     event_id: "id",
+    /// ).unwrap().into();
+    ///
+    /// let publish = to_binary_publish(&event).unwrap();
+    /// let packet = publish.to_v5_publish("events", QoS::AtLeastOnce);
+    /// assert_eq!(MqttPublish::from_v5_publish(&packet), publish);
+    /// ```
+    pub fn to_v5_publish(&self, topic: &str, qos: rumqttc::v5::mqttbytes::QoS) -> PublishV5 {
+        let properties = PublishProperties {
+            content_type: self.content_type.clone(),
+            user_properties: self.user_properties.clone(),
+            ..PublishProperties::default()
+        };
+        PublishV5::new(topic, qos, self.payload.clone(), Some(properties))
+    }
+}
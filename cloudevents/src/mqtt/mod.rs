@@ -0,0 +1,223 @@
+/*!
+MQTT protocol binding.
+
+Implements the MQTT protocol binding (see [v1.0 MQTT Protocol Binding](https://github.com/cloudevents/spec/blob/v1.0/mqtt-protocol-binding.md))
+on top of [`MqttPublish`], a client independent view of an MQTT PUBLISH packet:
+
+* With **MQTT 5**, events are carried in binary or structured content mode. In binary mode, the context
+  attributes and extensions are mapped to user properties of the same name, the content type attribute is
+  mapped to the `Content Type` property and the payload carries the raw data. In structured mode, the payload
+  carries the event in the JSON format, with the `application/cloudevents+json` content type. When reading a
+  packet, the content mode is detected from its `Content Type` property.
+* With **MQTT 3.1.1**, which has no packet properties, events are only carried in structured content mode,
+  and the payload always carries the event in the JSON format.
+
+Enable the `rumqttc` feature to convert packets from and to the PUBLISH packets of the
+[`rumqttc`](https://docs.rs/rumqttc) crate.
+
+# Example
+
+```
+use cloudevents::cloudevent;
+use cloudevents::mqtt::{from_publish, to_binary_publish, MqttVersion};
+use cloudevents::{CloudEvent, Data};
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    datacontenttype: "text/plain",
+    data: Data::from_string("hello world"),
+).unwrap().into();
+
+let publish = to_binary_publish(&event).unwrap();
+assert_eq!(publish.user_property("id"), Some("id"));
+assert_eq!(publish.content_type.as_deref(), Some("text/plain"));
+assert_eq!(publish.payload, b"hello world");
+
+assert_eq!(from_publish(&publish, MqttVersion::V5).unwrap(), event);
+```
+
+[`MqttPublish`]: struct.MqttPublish.html
+*/
+#[cfg(feature = "rumqttc")]
+mod adapter;
+
+use crate::message::{
+    data_from_bytes, data_to_bytes, ensure_json_format, ContentMode, EventDeserializer,
+    EventSerializer, CLOUDEVENTS_BATCH_JSON, CLOUDEVENTS_JSON,
+};
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+
+/// Versions of the MQTT protocol.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum MqttVersion {
+    /// MQTT 3.1.1, which only supports the structured content mode.
+    V3_1_1,
+    /// MQTT 5, which supports the binary and structured content modes.
+    V5,
+}
+
+/// The parts of an MQTT PUBLISH packet used by the protocol binding.
+///
+/// The topic, QoS and other delivery settings are left to the client.
+/// MQTT 3.1.1 packets only have a payload.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct MqttPublish {
+    /// The `Content Type` property.
+    pub content_type: Option<String>,
+    /// The `User Property` properties, in order.
+    pub user_properties: Vec<(String, String)>,
+    /// The payload of the packet.
+    pub payload: Vec<u8>,
+}
+
+impl MqttPublish {
+    /// Get the value of a user property.
+    ///
+    /// MQTT allows repeated user properties, in which case the first one is returned.
+    pub fn user_property(&self, name: &str) -> Option<&str> {
+        self.user_properties
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Create an MQTT 5 binary mode packet carrying the event.
+///
+/// # Errors
+///
+/// An error is returned if the data of the event cannot be serialized.
+pub fn to_binary_publish(event: &CloudEvent) -> Result<MqttPublish, Error> {
+    event.clone().deserialize_event(BinaryWriter {
+        publish: MqttPublish::default(),
+        content_type: None,
+    })
+}
+
+/// Create a structured mode packet carrying the event in the JSON format.
+///
+/// The content type is only set with [`MqttVersion::V5`].
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+///
+/// [`MqttVersion::V5`]: enum.MqttVersion.html#variant.V5
+pub fn to_structured_publish(
+    event: &CloudEvent,
+    version: MqttVersion,
+) -> Result<MqttPublish, Error> {
+    Ok(MqttPublish {
+        content_type: match version {
+            MqttVersion::V3_1_1 => None,
+            MqttVersion::V5 => Some(CLOUDEVENTS_JSON.to_owned()),
+        },
+        user_properties: Vec::new(),
+        payload: serde_json::to_vec(event)?,
+    })
+}
+
+/// Read the event carried by a packet of the given MQTT version.
+///
+/// MQTT 3.1.1 packets are read in structured mode, MQTT 5 packets in binary or structured mode.
+///
+/// # Errors
+///
+/// An error is returned if the packet is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_publish(publish: &MqttPublish, version: MqttVersion) -> Result<CloudEvent, Error> {
+    if version == MqttVersion::V3_1_1 {
+        return Ok(serde_json::from_slice(&publish.payload)?);
+    }
+    let content_type = publish.content_type.as_deref();
+    match ContentMode::from_content_type(content_type) {
+        ContentMode::Binary => BinaryReader(publish).into_event(),
+        ContentMode::Structured => {
+            ensure_json_format(content_type, CLOUDEVENTS_JSON)?;
+            Ok(serde_json::from_slice(&publish.payload)?)
+        }
+        ContentMode::Batch => Err(Error::UnsupportedContentType(
+            CLOUDEVENTS_BATCH_JSON.to_owned(),
+        )),
+    }
+}
+
+/// Writes the attributes and extensions to user properties, and the data to the payload.
+struct BinaryWriter {
+    publish: MqttPublish,
+    content_type: Option<&'static str>,
+}
+
+impl EventSerializer<MqttPublish> for BinaryWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.content_type = Some(version.content_type_attribute());
+        self.publish
+            .user_properties
+            .push(("specversion".to_owned(), version.as_str().to_owned()));
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        if self.content_type == Some(name) {
+            self.publish.content_type = Some(value);
+        } else {
+            self.publish.user_properties.push((name.to_owned(), value));
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        self.publish
+            .user_properties
+            .push((name.to_owned(), value.to_string()));
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<MqttPublish, Error> {
+        self.publish.payload = data_to_bytes(data)?;
+        Ok(self.publish)
+    }
+
+    fn end(self) -> Result<MqttPublish, Error> {
+        Ok(self.publish)
+    }
+}
+
+/// Reads the attributes and extensions from user properties, and the data from the payload.
+struct BinaryReader<'a>(&'a MqttPublish);
+
+impl<'a> EventDeserializer for BinaryReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let publish = self.0;
+        let spec_version: SpecVersion = match publish.user_property("specversion") {
+            Some(value) => value.parse()?,
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let attributes = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        for (name, value) in &publish.user_properties {
+            serializer = match name.as_str() {
+                "specversion" => serializer,
+                a if attributes.contains(&a) => serializer.set_attribute(a, value.clone())?,
+                a => serializer.set_extension(a, ExtensionValue::String(value.clone()))?,
+            };
+        }
+
+        let content_type = publish.content_type.as_deref();
+        if let Some(content_type) = content_type {
+            serializer = serializer.set_attribute(
+                spec_version.content_type_attribute(),
+                content_type.to_owned(),
+            )?;
+        }
+
+        if publish.payload.is_empty() {
+            serializer.end()
+        } else {
+            serializer.end_with_data(data_from_bytes(content_type, publish.payload.clone())?)
+        }
+    }
+}
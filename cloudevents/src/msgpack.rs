@@ -0,0 +1,47 @@
+/*!
+MessagePack structured encoding, available with the `msgpack` feature.
+
+Serializes events in MessagePack (see [MessagePack specification](https://github.com/msgpack/msgpack/blob/master/spec.md))
+with the same model as the JSON format: a map of the context attributes, the extensions and the data.
+As MessagePack is not human readable, binary data and binary extensions are encoded with the `bin` type
+instead of base64 strings, and binary data of spec version 1.0 is carried in `data` rather than `data_base64`.
+
+# Example
+
+```
+use cloudevents::msgpack::{from_bytes, to_bytes};
+use cloudevents::{cloudevent, CloudEvent, Data};
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    data: Data::from_binary(b"\x00\x01"),
+).unwrap().into();
+
+let bytes = to_bytes(&event).unwrap();
+assert_eq!(from_bytes(&bytes).unwrap(), event);
+```
+*/
+use crate::{CloudEvent, Error};
+
+/// Content type of a structured mode message in MessagePack.
+pub const CLOUDEVENTS_MSGPACK: &str = "application/cloudevents+msgpack";
+
+/// Serialize the event in MessagePack.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_bytes(event: &CloudEvent) -> Result<Vec<u8>, Error> {
+    Ok(rmp_serde::to_vec(event)?)
+}
+
+/// Parse an event in MessagePack.
+///
+/// # Errors
+///
+/// An error is returned if the input is not valid MessagePack, or if it does not carry a valid event.
+pub fn from_bytes(bytes: &[u8]) -> Result<CloudEvent, Error> {
+    Ok(rmp_serde::from_slice(bytes)?)
+}
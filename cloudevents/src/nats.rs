@@ -0,0 +1,237 @@
+/*!
+NATS protocol binding.
+
+Implements the NATS protocol binding (see [NATS Protocol Binding](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/nats-protocol-binding.md))
+on top of [`NatsMessage`], a client independent view of a NATS message, in two content modes:
+
+* **Binary**: the context attributes and extensions are mapped to `ce-` prefixed headers,
+  the content type attribute is mapped to the `content-type` header and the payload carries the raw data.
+* **Structured**: the payload carries the event in the JSON format. The `content-type` header is set to
+  `application/cloudevents+json`, and messages without a `ce-specversion` header are read in this mode
+  as well, as NATS servers before 2.2 do not support headers, and headers such as the `Nats-Msg-Id`
+  of JetStream can be set on any message.
+
+# Example
+
+```
+use cloudevents::nats::{from_message, to_binary_message};
+use cloudevents::{CloudEvent, CloudEventBuilder, Data};
+
+let event: CloudEvent = CloudEventBuilder::v1_0()
+    .event_type("test type")
+    .source("http://www.google.com")
+    .event_id("id")
+    .datacontenttype("text/plain")
+    .data(Data::from_string("hello world"))
+    .build()
+    .unwrap()
+    .into();
+
+let message = to_binary_message("events.greetings", &event).unwrap();
+assert_eq!(message.subject, "events.greetings");
+assert_eq!(message.header("ce-id"), Some("id"));
+assert_eq!(message.header("content-type"), Some("text/plain"));
+assert_eq!(message.payload, b"hello world");
+
+assert_eq!(from_message(&message).unwrap(), event);
+```
+
+[`NatsMessage`]: struct.NatsMessage.html
+*/
+use crate::message::{
+    data_from_bytes, data_to_bytes, ensure_json_format, ContentMode, EventDeserializer,
+    EventSerializer, CLOUDEVENTS_BATCH_JSON, CLOUDEVENTS_JSON,
+};
+use crate::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+
+/// Prefix of the headers carrying context attributes and extensions.
+const PREFIX: &str = "ce-";
+
+/// Header carrying the content type.
+const CONTENT_TYPE: &str = "content-type";
+
+/// Header carrying the spec version, which marks a binary mode message.
+const SPEC_VERSION: &str = "ce-specversion";
+
+/// A NATS message, independent of any client library.
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct NatsMessage {
+    /// The subject the message is published to.
+    pub subject: String,
+    /// The headers of the message, in order.
+    pub headers: Vec<(String, String)>,
+    /// The payload of the message.
+    pub payload: Vec<u8>,
+}
+
+impl NatsMessage {
+    /// Get the value of a header.
+    ///
+    /// Header names are compared case insensitively, and the first matching header is returned.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Create a binary mode message carrying the event, to publish to the `subject`.
+///
+/// # Errors
+///
+/// An error is returned if the data of the event cannot be serialized, or if an attribute
+/// or extension value contains a line break, which cannot be carried by a header.
+pub fn to_binary_message<S: Into<String>>(
+    subject: S,
+    event: &CloudEvent,
+) -> Result<NatsMessage, Error> {
+    event.clone().deserialize_event(BinaryWriter {
+        message: NatsMessage {
+            subject: subject.into(),
+            ..NatsMessage::default()
+        },
+        content_type: None,
+    })
+}
+
+/// Create a structured mode message carrying the event in the JSON format, to publish to the `subject`.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_structured_message<S: Into<String>>(
+    subject: S,
+    event: &CloudEvent,
+) -> Result<NatsMessage, Error> {
+    Ok(NatsMessage {
+        subject: subject.into(),
+        headers: vec![(CONTENT_TYPE.to_owned(), CLOUDEVENTS_JSON.to_owned())],
+        payload: serde_json::to_vec(event)?,
+    })
+}
+
+/// Read the event carried by a binary or structured mode message.
+///
+/// A message is read in binary mode if it has a `ce-specversion` header and its content type
+/// is not a CloudEvents format. Any other message is read in structured mode.
+///
+/// # Errors
+///
+/// An error is returned if the message is a batch, if its event format is not supported,
+/// or if it does not carry a valid event.
+pub fn from_message(message: &NatsMessage) -> Result<CloudEvent, Error> {
+    let content_type = message.header(CONTENT_TYPE);
+    match ContentMode::from_content_type(content_type) {
+        ContentMode::Binary if message.header(SPEC_VERSION).is_some() => {
+            BinaryReader(message).into_event()
+        }
+        ContentMode::Binary => Ok(serde_json::from_slice(&message.payload)?),
+        ContentMode::Structured => {
+            ensure_json_format(content_type, CLOUDEVENTS_JSON)?;
+            Ok(serde_json::from_slice(&message.payload)?)
+        }
+        ContentMode::Batch => Err(Error::UnsupportedContentType(
+            CLOUDEVENTS_BATCH_JSON.to_owned(),
+        )),
+    }
+}
+
+/// Writes the attributes and extensions to headers, and the data to the payload.
+struct BinaryWriter {
+    message: NatsMessage,
+    content_type: Option<&'static str>,
+}
+
+impl BinaryWriter {
+    fn push(&mut self, name: &str, value: String) {
+        self.message
+            .headers
+            .push((format!("{}{}", PREFIX, name), value));
+    }
+}
+
+/// Ensure the value can be carried by a header, which ends at the first line break.
+fn header_value(value: String, invalid: impl FnOnce() -> Error) -> Result<String, Error> {
+    if value.contains(&['\r', '\n'][..]) {
+        Err(invalid())
+    } else {
+        Ok(value)
+    }
+}
+
+impl EventSerializer<NatsMessage> for BinaryWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.content_type = Some(version.content_type_attribute());
+        self.push("specversion", version.as_str().to_owned());
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        let value = header_value(value, || Error::InvalidAttributeValue(name.to_owned()))?;
+        if self.content_type == Some(name) {
+            self.message.headers.push((CONTENT_TYPE.to_owned(), value));
+        } else {
+            self.push(name, value);
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let value = header_value(value.to_string(), || {
+            Error::InvalidExtensionValue(name.to_owned())
+        })?;
+        self.push(name, value);
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<NatsMessage, Error> {
+        self.message.payload = data_to_bytes(data)?;
+        Ok(self.message)
+    }
+
+    fn end(self) -> Result<NatsMessage, Error> {
+        Ok(self.message)
+    }
+}
+
+/// Reads the attributes and extensions from headers, and the data from the payload.
+struct BinaryReader<'a>(&'a NatsMessage);
+
+impl<'a> EventDeserializer for BinaryReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let message = self.0;
+        let spec_version: SpecVersion = match message.header(SPEC_VERSION) {
+            Some(value) => value.parse()?,
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let attributes = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        for (name, value) in &message.headers {
+            let name = name.to_ascii_lowercase();
+            if !name.starts_with(PREFIX) {
+                continue;
+            }
+            serializer = match &name[PREFIX.len()..] {
+                "specversion" => serializer,
+                a if attributes.contains(&a) => serializer.set_attribute(a, value.clone())?,
+                a => serializer.set_extension(a, ExtensionValue::String(value.clone()))?,
+            };
+        }
+
+        let content_type = message.header(CONTENT_TYPE);
+        if let Some(content_type) = content_type {
+            serializer = serializer.set_attribute(
+                spec_version.content_type_attribute(),
+                content_type.to_owned(),
+            )?;
+        }
+
+        if message.payload.is_empty() {
+            serializer.end()
+        } else {
+            serializer.end_with_data(data_from_bytes(content_type, message.payload.clone())?)
+        }
+    }
+}
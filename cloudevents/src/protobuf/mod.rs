@@ -0,0 +1,319 @@
+/*!
+Protobuf event format, available with the `protobuf` feature.
+
+Implements the Protobuf event format (see [Protobuf Event Format for CloudEvents](https://github.com/cloudevents/spec/blob/main/cloudevents/formats/protobuf-format.md))
+with the messages of the `io.cloudevents.v1` package, defined in the [`proto`](proto/index.html) module and
+shipped in `proto/cloudevents.proto` for other languages. The format only supports spec version 1.0,
+use [`CloudEvent::to_version`] to convert events of other spec versions.
+
+The required attributes are mapped to the fields of the same name, the optional attributes and the extensions
+to the `attributes` map with their type, and the data is mapped as follows:
+
+* Binary data is carried in `binary_data`, or in `proto_data` if `datacontenttype` is `application/protobuf`,
+  in which case `dataschema` is used as type URL. When reading `proto_data`, these two attributes are populated
+  from the message if they are missing, `dataschema` only if the type URL is an absolute URI.
+* String and JSON data are carried in `text_data`. When reading `text_data`, data with a JSON content type is
+  parsed as JSON.
+
+# Example
+
+```
+use cloudevents::protobuf::{from_bytes, to_bytes};
+use cloudevents::v1_0::CloudEventV1_0Builder;
+use cloudevents::Data;
+
+let event = CloudEventV1_0Builder::default()
+    .event_type("test type")
+    .source("http://www.google.com")
+    .event_id("id")
+    .extension("count", 42)
+    .data(Data::from_binary(b"bytes"))
+    .build()
+    .unwrap();
+
+let bytes = to_bytes(&event).unwrap();
+assert_eq!(from_bytes(&bytes).unwrap(), event);
+```
+
+[`CloudEvent::to_version`]: ../enum.CloudEvent.html#method.to_version
+*/
+pub mod proto;
+
+use self::proto::cloud_event::cloud_event_attribute_value::Attr;
+use self::proto::cloud_event::{self, CloudEventAttributeValue};
+use self::proto::CloudEventBatch;
+use crate::message::{
+    essence, expect_spec_version, format_time, is_json, EventDeserializer, EventSerializer,
+};
+use crate::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use crate::{Data, Error, ExtensionValue, SpecVersion};
+use chrono::prelude::{DateTime, FixedOffset, TimeZone, Utc};
+use prost::Message;
+use std::convert::TryFrom;
+use url::Url;
+
+/// Content type of a structured mode message in the Protobuf format.
+pub const CLOUDEVENTS_PROTOBUF: &str = "application/cloudevents+protobuf";
+
+/// Content type of a batch mode message in the Protobuf format.
+pub const CLOUDEVENTS_BATCH_PROTOBUF: &str = "application/cloudevents-batch+protobuf";
+
+/// Content type of data carried in `proto_data`.
+const PROTOBUF: &str = "application/protobuf";
+
+/// Serialize the event in the Protobuf format.
+///
+/// # Errors
+///
+/// An error is returned if an extension cannot be represented in the format,
+/// e.g. an integer out of the 32 bits range.
+pub fn to_bytes(event: &CloudEventV1_0) -> Result<Vec<u8>, Error> {
+    Ok(proto::CloudEvent::try_from(event.clone())?.encode_to_vec())
+}
+
+/// Parse an event in the Protobuf format.
+///
+/// # Errors
+///
+/// An error is returned if the input is not a valid message, or if it does not carry a valid event.
+pub fn from_bytes(bytes: &[u8]) -> Result<CloudEventV1_0, Error> {
+    CloudEventV1_0::try_from(proto::CloudEvent::decode(bytes)?)
+}
+
+/// Serialize the events in the Protobuf batch format.
+///
+/// # Errors
+///
+/// See [`to_bytes`].
+///
+/// [`to_bytes`]: fn.to_bytes.html
+pub fn batch_to_bytes(events: &[CloudEventV1_0]) -> Result<Vec<u8>, Error> {
+    let batch = CloudEventBatch {
+        events: events
+            .iter()
+            .cloned()
+            .map(proto::CloudEvent::try_from)
+            .collect::<Result<_, _>>()?,
+    };
+    Ok(batch.encode_to_vec())
+}
+
+/// Parse events in the Protobuf batch format.
+///
+/// # Errors
+///
+/// See [`from_bytes`].
+///
+/// [`from_bytes`]: fn.from_bytes.html
+pub fn batch_from_bytes(bytes: &[u8]) -> Result<Vec<CloudEventV1_0>, Error> {
+    CloudEventBatch::decode(bytes)?
+        .events
+        .into_iter()
+        .map(CloudEventV1_0::try_from)
+        .collect()
+}
+
+impl TryFrom<CloudEventV1_0> for proto::CloudEvent {
+    type Error = Error;
+
+    fn try_from(event: CloudEventV1_0) -> Result<Self, Error> {
+        event.deserialize_event(ProtoWriter::default())
+    }
+}
+
+impl TryFrom<proto::CloudEvent> for CloudEventV1_0 {
+    type Error = Error;
+
+    fn try_from(event: proto::CloudEvent) -> Result<Self, Error> {
+        event.deserialize_event(CloudEventV1_0Builder::default())
+    }
+}
+
+/// Writes an event to a `CloudEvent` message.
+#[derive(Default)]
+struct ProtoWriter {
+    event: proto::CloudEvent,
+    datacontenttype: Option<String>,
+    dataschema: Option<String>,
+}
+
+impl ProtoWriter {
+    fn insert(&mut self, name: &str, attr: Attr) {
+        self.event.attributes.insert(
+            name.to_owned(),
+            CloudEventAttributeValue { attr: Some(attr) },
+        );
+    }
+}
+
+impl EventSerializer<proto::CloudEvent> for ProtoWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V1_0, version)?;
+        self.event.spec_version = version.as_str().to_owned();
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        match name {
+            "id" => self.event.id = value,
+            "source" => self.event.source = value,
+            "type" => self.event.r#type = value,
+            "time" => {
+                let time = DateTime::parse_from_rfc3339(&value)
+                    .map_err(|source| Error::InvalidTime { source })?;
+                self.insert(name, Attr::CeTimestamp(timestamp(&time)));
+            }
+            "dataschema" => {
+                self.dataschema = Some(value.clone());
+                self.insert(name, Attr::CeUri(value));
+            }
+            "datacontenttype" => {
+                self.datacontenttype = Some(value.clone());
+                self.insert(name, Attr::CeString(value));
+            }
+            "subject" => self.insert(name, Attr::CeString(value)),
+            _ => return Err(Error::UnknownAttribute(name.to_owned())),
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let attr = match value {
+            ExtensionValue::Boolean(b) => Attr::CeBoolean(b),
+            ExtensionValue::Integer(i) => Attr::CeInteger(
+                i32::try_from(i).map_err(|_| Error::InvalidExtensionValue(name.to_owned()))?,
+            ),
+            ExtensionValue::String(s) => Attr::CeString(s),
+            ExtensionValue::Binary(b) => Attr::CeBytes(b),
+            ExtensionValue::Uri(u) => Attr::CeUri(u.as_str().to_owned()),
+            ExtensionValue::UriRef(u) => Attr::CeUriRef(u),
+            ExtensionValue::Timestamp(t) => Attr::CeTimestamp(timestamp(&t)),
+            ExtensionValue::Object(_) => return Err(Error::InvalidExtensionValue(name.to_owned())),
+        };
+        self.insert(name, attr);
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<proto::CloudEvent, Error> {
+        let is_protobuf = self.datacontenttype.as_deref().map(essence).as_deref() == Some(PROTOBUF);
+        self.event.data = Some(match data {
+            Data::Binary(value) if is_protobuf && self.dataschema.is_some() => {
+                cloud_event::Data::ProtoData(prost_types::Any {
+                    type_url: self.dataschema.take().unwrap_or_default(),
+                    value,
+                })
+            }
+            Data::Binary(bytes) => cloud_event::Data::BinaryData(bytes),
+            Data::StringOrBinary(text) => cloud_event::Data::TextData(text),
+            Data::Object(value) => cloud_event::Data::TextData(serde_json::to_string(&value)?),
+        });
+        Ok(self.event)
+    }
+
+    fn end(self) -> Result<proto::CloudEvent, Error> {
+        Ok(self.event)
+    }
+}
+
+impl EventDeserializer for proto::CloudEvent {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let spec_version: SpecVersion = self.spec_version.parse()?;
+        let names = spec_version.attribute_names();
+
+        let mut attributes: Vec<_> = self.attributes.into_iter().collect();
+        attributes.sort_by(|(a, _), (b, _)| a.cmp(b));
+        if let Some(cloud_event::Data::ProtoData(any)) = &self.data {
+            if !attributes.iter().any(|(name, _)| name == "datacontenttype") {
+                attributes.push(("datacontenttype".to_owned(), string_value(PROTOBUF)));
+            }
+            if !attributes.iter().any(|(name, _)| name == "dataschema")
+                && Url::parse(&any.type_url).is_ok()
+            {
+                attributes.push(("dataschema".to_owned(), string_value(&any.type_url)));
+            }
+        }
+
+        let mut serializer = serializer
+            .set_spec_version(spec_version)?
+            .set_attribute("type", self.r#type)?
+            .set_attribute("source", self.source)?
+            .set_attribute("id", self.id)?;
+        let mut datacontenttype = None;
+        for (name, value) in attributes {
+            let attr = value
+                .attr
+                .ok_or_else(|| Error::InvalidAttributeValue(name.clone()))?;
+            serializer = if names.contains(&name.as_str()) {
+                let value = attribute_string(&name, attr)?;
+                if name == spec_version.content_type_attribute() {
+                    datacontenttype = Some(value.clone());
+                }
+                serializer.set_attribute(&name, value)?
+            } else {
+                let value = extension_value(&name, attr)?;
+                serializer.set_extension(&name, value)?
+            };
+        }
+
+        match self.data {
+            Some(cloud_event::Data::BinaryData(bytes)) => {
+                serializer.end_with_data(Data::Binary(bytes))
+            }
+            Some(cloud_event::Data::ProtoData(any)) => {
+                serializer.end_with_data(Data::Binary(any.value))
+            }
+            Some(cloud_event::Data::TextData(text)) => {
+                let data = match datacontenttype.as_deref().map(essence) {
+                    Some(t) if is_json(&t) => Data::Object(serde_json::from_str(&text)?),
+                    _ => Data::StringOrBinary(text),
+                };
+                serializer.end_with_data(data)
+            }
+            None => serializer.end(),
+        }
+    }
+}
+
+fn string_value(value: &str) -> CloudEventAttributeValue {
+    CloudEventAttributeValue {
+        attr: Some(Attr::CeString(value.to_owned())),
+    }
+}
+
+/// Get the string representation of a context attribute.
+fn attribute_string(name: &str, attr: Attr) -> Result<String, Error> {
+    match attr {
+        Attr::CeString(s) | Attr::CeUri(s) | Attr::CeUriRef(s) => Ok(s),
+        Attr::CeTimestamp(t) => Ok(format_time(&datetime(name, &t)?)),
+        _ => Err(Error::InvalidAttributeValue(name.to_owned())),
+    }
+}
+
+fn extension_value(name: &str, attr: Attr) -> Result<ExtensionValue, Error> {
+    Ok(match attr {
+        Attr::CeBoolean(b) => ExtensionValue::Boolean(b),
+        Attr::CeInteger(i) => ExtensionValue::Integer(i64::from(i)),
+        Attr::CeString(s) => ExtensionValue::String(s),
+        Attr::CeBytes(b) => ExtensionValue::Binary(b),
+        Attr::CeUri(u) => ExtensionValue::Uri(
+            Url::parse(&u).map_err(|_| Error::InvalidExtensionValue(name.to_owned()))?,
+        ),
+        Attr::CeUriRef(u) => ExtensionValue::UriRef(u),
+        Attr::CeTimestamp(t) => ExtensionValue::Timestamp(datetime(name, &t)?),
+    })
+}
+
+fn timestamp(time: &DateTime<FixedOffset>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn datetime(name: &str, t: &prost_types::Timestamp) -> Result<DateTime<FixedOffset>, Error> {
+    u32::try_from(t.nanos)
+        .ok()
+        .and_then(|nanos| Utc.timestamp_opt(t.seconds, nanos).single())
+        .map(DateTime::from)
+        .ok_or_else(|| Error::InvalidAttributeValue(name.to_owned()))
+}
@@ -0,0 +1,95 @@
+//! Messages of the `io.cloudevents.v1` package, as defined in `proto/cloudevents.proto`.
+
+use std::collections::HashMap;
+
+/// The `io.cloudevents.v1.CloudEvent` message.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloudEvent {
+    /// The `id` attribute.
+    #[prost(string, tag = "1")]
+    pub id: String,
+    /// The `source` attribute.
+    #[prost(string, tag = "2")]
+    pub source: String,
+    /// The `specversion` attribute.
+    #[prost(string, tag = "3")]
+    pub spec_version: String,
+    /// The `type` attribute.
+    #[prost(string, tag = "4")]
+    pub r#type: String,
+    /// The optional attributes and the extensions.
+    #[prost(map = "string, message", tag = "5")]
+    pub attributes: HashMap<String, cloud_event::CloudEventAttributeValue>,
+    /// The data.
+    #[prost(oneof = "cloud_event::Data", tags = "6, 7, 8")]
+    pub data: Option<cloud_event::Data>,
+}
+
+/// Nested messages and enums of [`CloudEvent`].
+///
+/// [`CloudEvent`]: struct.CloudEvent.html
+pub mod cloud_event {
+    /// The `io.cloudevents.v1.CloudEvent.CloudEventAttributeValue` message.
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct CloudEventAttributeValue {
+        /// The typed value.
+        #[prost(
+            oneof = "cloud_event_attribute_value::Attr",
+            tags = "1, 2, 3, 4, 5, 6, 7"
+        )]
+        pub attr: Option<cloud_event_attribute_value::Attr>,
+    }
+
+    /// Nested messages and enums of [`CloudEventAttributeValue`].
+    ///
+    /// [`CloudEventAttributeValue`]: struct.CloudEventAttributeValue.html
+    pub mod cloud_event_attribute_value {
+        /// The `attr` oneof, one variant per type of the CloudEvents type system.
+        #[derive(Clone, PartialEq, ::prost::Oneof)]
+        pub enum Attr {
+            /// A `Boolean` value.
+            #[prost(bool, tag = "1")]
+            CeBoolean(bool),
+            /// An `Integer` value.
+            #[prost(int32, tag = "2")]
+            CeInteger(i32),
+            /// A `String` value.
+            #[prost(string, tag = "3")]
+            CeString(String),
+            /// A `Binary` value.
+            #[prost(bytes, tag = "4")]
+            CeBytes(Vec<u8>),
+            /// A `URI` value.
+            #[prost(string, tag = "5")]
+            CeUri(String),
+            /// A `URI-reference` value.
+            #[prost(string, tag = "6")]
+            CeUriRef(String),
+            /// A `Timestamp` value.
+            #[prost(message, tag = "7")]
+            CeTimestamp(::prost_types::Timestamp),
+        }
+    }
+
+    /// The `data` oneof.
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Data {
+        /// Binary data.
+        #[prost(bytes, tag = "6")]
+        BinaryData(Vec<u8>),
+        /// Text data, including JSON.
+        #[prost(string, tag = "7")]
+        TextData(String),
+        /// A protobuf message.
+        #[prost(message, tag = "8")]
+        ProtoData(::prost_types::Any),
+    }
+}
+
+/// The `io.cloudevents.v1.CloudEventBatch` message.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloudEventBatch {
+    /// The events of the batch.
+    #[prost(message, repeated, tag = "1")]
+    pub events: Vec<CloudEvent>,
+}
@@ -1,10 +1,11 @@
 use super::CloudEventV0_2;
+use crate::message::{expect_spec_version, EventSerializer};
+use crate::validation::{self, Violations};
 use crate::Data;
+use crate::Error;
 use crate::ExtensionValue;
-use chrono::prelude::DateTime;
-use failure::{format_err, Error};
+use crate::SpecVersion;
 use std::collections::HashMap;
-use url::{ParseError, Url};
 
 /// Create a new [`CloudEvent`] according to spec version 0.2.
 ///
@@ -12,7 +13,7 @@ use url::{ParseError, Url};
 ///
 /// ```
 /// use cloudevents::v0_2::{CloudEventV0_2, CloudEventV0_2Builder};
-/// use failure::Error;
+/// use cloudevents::Error;
 ///
 /// let event : Result<CloudEventV0_2, Error> = CloudEventV0_2Builder::default()
 ///     .event_id("id")
@@ -23,7 +24,7 @@ use url::{ParseError, Url};
 /// ```
 ///
 /// [`CloudEvent`]: struct.CloudEventV0_2.html
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CloudEventV0_2Builder {
     event_type: Option<String>,
     source: Option<String>,
@@ -84,66 +85,118 @@ impl CloudEventV0_2Builder {
         self
     }
 
+    /// Add an extension.
+    pub fn extension<S: Into<String>, V: Into<ExtensionValue>>(
+        mut self,
+        name: S,
+        value: V,
+    ) -> Self {
+        self.extensions
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
     /// Build a [`CloudEvent`].
     ///
     /// # Errors
     ///
     /// An error is thrown if one of the required fields (event_type, id or source) is not populated,
-    /// or if one of the validated fields (time, source and schemeurl) are populated with an invalid value.
+    /// or if one of the validated fields (time, source and schemaurl) is populated with an invalid value.
+    /// The remaining rules of the spec are checked by [`Validate`].
+    /// Only the first error is returned, use [`build_collecting_errors`] to get all of them.
     ///
-    /// [`CloudEvent`]: struct.CloudEvent.html
+    /// [`CloudEvent`]: struct.CloudEventV0_2.html
+    /// [`build_collecting_errors`]: #method.build_collecting_errors
+    /// [`Validate`]: ../trait.Validate.html
     pub fn build(self) -> Result<CloudEventV0_2, Error> {
-        Ok(CloudEventV0_2::new(
-            self.event_type
-                .ok_or(format_err!("Event type is required"))?,
-            {
-                if let Some(x) = self.source {
-                    let source = x;
-                    match Url::parse(&source) {
-                        Ok(_) | Err(ParseError::RelativeUrlWithoutBase) => source,
-                        Err(e) => return Err(format_err!("{}", e)),
-                    }
-                } else {
-                    return Err(format_err!("Source is required"));
-                }
-            },
-            self.id.ok_or(format_err!("Event id is required"))?,
-            {
-                if let Some(t) = self.time {
-                    Some(DateTime::parse_from_rfc3339(&t)?)
-                } else {
-                    None
-                }
-            },
-            {
-                if let Some(x) = self.schemaurl {
-                    let schemaurl = x;
-                    match Url::parse(&schemaurl) {
-                        Ok(_) | Err(ParseError::RelativeUrlWithoutBase) => Some(schemaurl),
-                        Err(e) => return Err(format_err!("{}", e)),
-                    }
-                } else {
-                    None
-                }
-            },
-            self.contenttype,
-            self.data,
-            self.extensions,
-        ))
+        self.build_collecting_errors()
+            .map_err(|mut errors| errors.remove(0))
     }
-}
 
-impl Default for CloudEventV0_2Builder {
-    fn default() -> Self {
-        CloudEventV0_2Builder {
-            event_type: None,
-            id: None,
-            schemaurl: None,
-            source: None,
-            extensions: None,
-            data: None,
-            contenttype: None,
-            time: None,
+    /// Build a [`CloudEvent`], validating every attribute instead of stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// The errors of all missing or invalid attributes are returned, in the order the
+    /// attributes are defined by the spec.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::CloudEventBuilder;
+    ///
+    /// let errors = CloudEventBuilder::v0_2()
+    ///     .source("http://www.google.com:port")
+    ///     .time("yesterday")
+    ///     .build_collecting_errors()
+    ///     .unwrap_err();
+    ///
+    /// let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    /// assert_eq!(attributes, vec!["type", "source", "id", "time"]);
+    /// ```
+    ///
+    /// [`CloudEvent`]: struct.CloudEventV0_2.html
+    pub fn build_collecting_errors(self) -> Result<CloudEventV0_2, Vec<Error>> {
+        let mut violations = Violations::default();
+        violations.check(validation::required("type", self.event_type.as_ref()));
+        violations.check(
+            validation::required("source", self.source.as_deref())
+                .and_then(|source| validation::uri_ref("source", source)),
+        );
+        violations.check(validation::required("id", self.id.as_ref()));
+        let time = violations
+            .check(self.time.as_deref().map(validation::time).transpose())
+            .flatten();
+        if let Some(schemaurl) = &self.schemaurl {
+            violations.check(validation::uri_ref("schemaurl", schemaurl));
         }
+
+        match (self.event_type, self.source, self.id) {
+            (Some(event_type), Some(source), Some(id)) if violations.is_empty() => {
+                Ok(CloudEventV0_2::new(
+                    event_type,
+                    source,
+                    id,
+                    time,
+                    self.schemaurl,
+                    self.contenttype,
+                    self.data,
+                    self.extensions,
+                ))
+            }
+            _ => Err(violations.into_errors()),
+        }
+    }
+}
+
+impl EventSerializer<CloudEventV0_2> for CloudEventV0_2Builder {
+    fn set_spec_version(self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V0_2, version)?;
+        Ok(self)
+    }
+
+    fn set_attribute(self, name: &str, value: String) -> Result<Self, Error> {
+        Ok(match name {
+            "type" => self.event_type(value),
+            "source" => self.source(value),
+            "id" => self.event_id(value),
+            "time" => self.time(value),
+            "schemaurl" => self.schemaurl(value),
+            "contenttype" => self.contenttype(value),
+            _ => return Err(Error::UnknownAttribute(name.to_owned())),
+        })
+    }
+
+    fn set_extension(self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        Ok(self.extension(name, value))
+    }
+
+    fn end_with_data(self, data: Data) -> Result<CloudEventV0_2, Error> {
+        self.data(data).build()
+    }
+
+    fn end(self) -> Result<CloudEventV0_2, Error> {
+        self.build()
     }
 }
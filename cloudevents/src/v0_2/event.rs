@@ -1,35 +1,42 @@
+use crate::event::duplicate_attribute;
+use crate::message::{end_with_extensions, format_time, EventDeserializer, EventSerializer};
+use crate::validation::{self, Violations};
 use crate::Data;
 use crate::ExtensionValue;
+use crate::{Error, SpecVersion, Validate};
 use chrono::prelude::{DateTime, FixedOffset};
-use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, MapAccess, Unexpected, Visitor,
+};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 /// CloudEvent according to spec version 0.2
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+///
+/// In the JSON format, extension attributes are serialized as top-level members next to
+/// the context attributes. When deserializing, every unknown top-level member is collected
+/// as an extension. As the v0.2 JSON format has no way to mark binary data, [`Data::Binary`]
+/// is serialized as base64 encoded `data` and deserialized as [`Data::StringOrBinary`]. With formats
+/// which are not human readable, such as CBOR, it is serialized as a byte string instead.
+///
+/// [`Data::Binary`]: ../enum.Data.html#variant.Binary
+/// [`Data::StringOrBinary`]: ../enum.Data.html#variant.StringOrBinary
+#[derive(Debug, PartialEq, Clone)]
 pub struct CloudEventV0_2 {
-    #[serde(rename = "type")]
-    event_type: String,
-    specversion: String,
-    source: String,
-    id: String,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    time: Option<DateTime<FixedOffset>>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    schemaurl: Option<String>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    contenttype: Option<String>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    data: Option<Data>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    extensions: Option<HashMap<String, ExtensionValue>>,
+    pub(crate) event_type: String,
+    pub(crate) specversion: String,
+    pub(crate) source: String,
+    pub(crate) id: String,
+    pub(crate) time: Option<DateTime<FixedOffset>>,
+    pub(crate) schemaurl: Option<String>,
+    pub(crate) contenttype: Option<String>,
+    pub(crate) data: Option<Data>,
+    pub(crate) extensions: Option<HashMap<String, ExtensionValue>>,
 }
 
 impl CloudEventV0_2 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         event_type: String,
         source: String,
@@ -88,8 +95,199 @@ impl CloudEventV0_2 {
         self.contenttype.as_ref().map(|x| x.as_ref())
     }
 
+    /// Decode the data into `T` according to its content type.
+    ///
+    /// The data is decoded according to the `contenttype` attribute:
+    ///
+    /// * Without a content type, or with `application/json` or a `+json` suffix, the data is
+    ///   parsed as JSON, whether it is held as a JSON value, a string or binary data.
+    /// * With a `text/*` content type, the data is read as a string, binary data being decoded
+    ///   as UTF-8.
+    /// * With any other content type, binary data is read as a sequence of bytes, e.g. into
+    ///   a `Vec<u8>`.
+    ///
+    /// Returns `Ok(None)` if the event carries no data.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the content type is not supported for the data, e.g. string
+    /// data with an `image/png` content type, or if the data cannot be decoded into `T`.
+    pub fn data_as<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.data
+            .as_ref()
+            .map(|data| data.decode(self.contenttype(), None))
+            .transpose()
+    }
+
     /// Get the extensions
     pub fn extensions(&self) -> Option<&HashMap<String, ExtensionValue>> {
         self.extensions.as_ref()
     }
 }
+
+impl Validate for CloudEventV0_2 {
+    fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut violations = Violations::default();
+        validation::context(
+            &mut violations,
+            Some(&self.event_type),
+            Some(&self.source),
+            Some(&self.id),
+        );
+        validate_optional(
+            &mut violations,
+            self.schemaurl.as_deref(),
+            self.contenttype.as_deref(),
+            self.extensions.as_ref(),
+        );
+        violations.into_result()
+    }
+}
+
+/// Validate the optional attributes and the extensions according to spec version 0.2.
+fn validate_optional(
+    violations: &mut Violations,
+    schemaurl: Option<&str>,
+    contenttype: Option<&str>,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    if let Some(schemaurl) = schemaurl {
+        violations.check(validation::uri("schemaurl", schemaurl));
+    }
+    if let Some(contenttype) = contenttype {
+        violations.check(validation::media_type("contenttype", contenttype));
+    }
+    validation::extension_names(violations, extensions);
+}
+
+impl EventDeserializer for CloudEventV0_2 {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let mut s = serializer
+            .set_spec_version(SpecVersion::V0_2)?
+            .set_attribute("type", self.event_type)?
+            .set_attribute("source", self.source)?
+            .set_attribute("id", self.id)?;
+        if let Some(time) = self.time {
+            s = s.set_attribute("time", format_time(&time))?;
+        }
+        if let Some(schemaurl) = self.schemaurl {
+            s = s.set_attribute("schemaurl", schemaurl)?;
+        }
+        if let Some(contenttype) = self.contenttype {
+            s = s.set_attribute("contenttype", contenttype)?;
+        }
+        end_with_extensions(s, self.extensions, self.data)
+    }
+}
+
+impl Serialize for CloudEventV0_2 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.event_type)?;
+        map.serialize_entry("specversion", &self.specversion)?;
+        map.serialize_entry("source", &self.source)?;
+        map.serialize_entry("id", &self.id)?;
+        if let Some(time) = &self.time {
+            map.serialize_entry("time", time)?;
+        }
+        if let Some(schemaurl) = &self.schemaurl {
+            map.serialize_entry("schemaurl", schemaurl)?;
+        }
+        if let Some(contenttype) = &self.contenttype {
+            map.serialize_entry("contenttype", contenttype)?;
+        }
+        if let Some(data) = &self.data {
+            map.serialize_entry("data", data)?;
+        }
+        if let Some(extensions) = &self.extensions {
+            for (name, value) in extensions {
+                map.serialize_entry(name, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CloudEventV0_2 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(CloudEventV0_2Visitor)
+    }
+}
+
+struct CloudEventV0_2Visitor;
+
+impl<'de> Visitor<'de> for CloudEventV0_2Visitor {
+    type Value = CloudEventV0_2;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent according to spec version 0.2")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut event_type = None;
+        let mut specversion: Option<String> = None;
+        let mut source = None;
+        let mut id = None;
+        let mut time = None;
+        let mut schemaurl = None;
+        let mut contenttype = None;
+        let mut data = None;
+        let mut extensions = HashMap::new();
+        let mut names = HashSet::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            if !names.insert(key.clone()) {
+                return Err(duplicate_attribute(&key));
+            }
+            match key.as_str() {
+                "type" => event_type = Some(map.next_value()?),
+                "specversion" => specversion = Some(map.next_value()?),
+                "source" => source = Some(map.next_value()?),
+                "id" => id = Some(map.next_value()?),
+                "time" => time = map.next_value()?,
+                "schemaurl" => schemaurl = map.next_value()?,
+                "contenttype" => contenttype = map.next_value()?,
+                "data" => data = map.next_value()?,
+                _ => {
+                    extensions.insert(key, map.next_value()?);
+                }
+            }
+        }
+
+        let specversion = specversion.ok_or_else(|| de::Error::missing_field("specversion"))?;
+        if specversion != "0.2" {
+            return Err(de::Error::invalid_value(
+                Unexpected::Str(&specversion),
+                &"0.2",
+            ));
+        }
+
+        Ok(CloudEventV0_2 {
+            event_type: event_type.ok_or_else(|| de::Error::missing_field("type"))?,
+            specversion,
+            source: source.ok_or_else(|| de::Error::missing_field("source"))?,
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            time,
+            schemaurl,
+            contenttype,
+            data,
+            extensions: if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        })
+    }
+}
@@ -6,7 +6,7 @@
 use cloudevents::cloudevent_v0_2;
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::v0_2::CloudEventV0_2;
-use failure::Error;
+use cloudevents::Error;
 
 let event : Result<CloudEventV0_2, Error> = cloudevent_v0_2!(
     event_type: "test type",
@@ -22,7 +22,7 @@ let event : Result<CloudEventV0_2, Error> = cloudevent_v0_2!(
 ```
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::v0_2::CloudEventV0_2;
-use failure::Error;
+use cloudevents::Error;
 
 let event : Result<CloudEventV0_2, Error> = CloudEventBuilder::v0_2()
   .event_id("id")
@@ -0,0 +1,227 @@
+use super::CloudEventV0_3;
+use crate::message::{expect_spec_version, EventSerializer};
+use crate::validation::{self, Violations};
+use crate::Data;
+use crate::Error;
+use crate::ExtensionValue;
+use crate::SpecVersion;
+use std::collections::HashMap;
+
+/// Create a new [`CloudEvent`] according to spec version 0.3.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::v0_3::{CloudEventV0_3, CloudEventV0_3Builder};
+/// use cloudevents::Error;
+///
+/// let event : Result<CloudEventV0_3, Error> = CloudEventV0_3Builder::default()
+///     .event_id("id")
+///     .source("http://www.google.com")
+///     .event_type("test type")
+///     .datacontenttype("application/json")
+///     .build();
+/// ```
+///
+/// [`CloudEvent`]: struct.CloudEventV0_3.html
+#[derive(Debug, Default)]
+pub struct CloudEventV0_3Builder {
+    event_type: Option<String>,
+    source: Option<String>,
+    id: Option<String>,
+    time: Option<String>,
+    subject: Option<String>,
+    schemaurl: Option<String>,
+    datacontenttype: Option<String>,
+    datacontentencoding: Option<String>,
+    data: Option<Data>,
+    extensions: Option<HashMap<String, ExtensionValue>>,
+}
+
+impl CloudEventV0_3Builder {
+    /// Set the event type.
+    pub fn event_type<S: Into<String>>(mut self, s: S) -> Self {
+        self.event_type = Some(s.into());
+        self
+    }
+
+    /// Set the source.
+    pub fn source<S: Into<String>>(mut self, s: S) -> Self {
+        self.source = Some(s.into());
+        self
+    }
+
+    /// Set the event id.
+    pub fn event_id<S: Into<String>>(mut self, s: S) -> Self {
+        self.id = Some(s.into());
+        self
+    }
+
+    /// Set the time.
+    pub fn time<S: Into<String>>(mut self, s: S) -> Self {
+        self.time = Some(s.into());
+        self
+    }
+
+    /// Set the subject.
+    pub fn subject<S: Into<String>>(mut self, s: S) -> Self {
+        self.subject = Some(s.into());
+        self
+    }
+
+    /// Set the schemaurl.
+    pub fn schemaurl<S: Into<String>>(mut self, s: S) -> Self {
+        self.schemaurl = Some(s.into());
+        self
+    }
+
+    /// Set the datacontenttype.
+    pub fn datacontenttype<S: Into<String>>(mut self, s: S) -> Self {
+        self.datacontenttype = Some(s.into());
+        self
+    }
+
+    /// Set the datacontentencoding.
+    pub fn datacontentencoding<S: Into<String>>(mut self, s: S) -> Self {
+        self.datacontentencoding = Some(s.into());
+        self
+    }
+
+    /// Set the data.
+    ///
+    /// Binary data sets the datacontentencoding to `base64`, unless another one is set.
+    pub fn data(mut self, d: Data) -> Self {
+        self.data = Some(d);
+        self
+    }
+
+    /// Set the extensions.
+    pub fn extensions(mut self, e: HashMap<String, ExtensionValue>) -> Self {
+        self.extensions = Some(e);
+        self
+    }
+
+    /// Add an extension.
+    pub fn extension<S: Into<String>, V: Into<ExtensionValue>>(
+        mut self,
+        name: S,
+        value: V,
+    ) -> Self {
+        self.extensions
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Build a [`CloudEvent`].
+    ///
+    /// # Errors
+    ///
+    /// An error is thrown if one of the required fields (event_type, id or source) is not populated,
+    /// or if one of the validated fields (time, source and schemaurl) is populated with an invalid value.
+    /// The remaining rules of the spec are checked by [`Validate`].
+    /// Only the first error is returned, use [`build_collecting_errors`] to get all of them.
+    ///
+    /// [`CloudEvent`]: struct.CloudEventV0_3.html
+    /// [`build_collecting_errors`]: #method.build_collecting_errors
+    /// [`Validate`]: ../trait.Validate.html
+    pub fn build(self) -> Result<CloudEventV0_3, Error> {
+        self.build_collecting_errors()
+            .map_err(|mut errors| errors.remove(0))
+    }
+
+    /// Build a [`CloudEvent`], validating every attribute instead of stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// The errors of all missing or invalid attributes are returned, in the order the
+    /// attributes are defined by the spec.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::CloudEventBuilder;
+    ///
+    /// let errors = CloudEventBuilder::v0_3()
+    ///     .source("http://www.google.com:port")
+    ///     .time("yesterday")
+    ///     .build_collecting_errors()
+    ///     .unwrap_err();
+    ///
+    /// let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    /// assert_eq!(attributes, vec!["type", "source", "id", "time"]);
+    /// ```
+    ///
+    /// [`CloudEvent`]: struct.CloudEventV0_3.html
+    pub fn build_collecting_errors(self) -> Result<CloudEventV0_3, Vec<Error>> {
+        let mut violations = Violations::default();
+        violations.check(validation::required("type", self.event_type.as_ref()));
+        violations.check(
+            validation::required("source", self.source.as_deref())
+                .and_then(|source| validation::uri_ref("source", source)),
+        );
+        violations.check(validation::required("id", self.id.as_ref()));
+        let time = violations
+            .check(
+                self.time
+                    .as_deref()
+                    .map(validation::time_or_now)
+                    .transpose(),
+            )
+            .flatten();
+        if let Some(schemaurl) = &self.schemaurl {
+            violations.check(validation::uri_ref("schemaurl", schemaurl));
+        }
+
+        match (self.event_type, self.source, self.id) {
+            (Some(event_type), Some(source), Some(id)) if violations.is_empty() => {
+                Ok(CloudEventV0_3::new(
+                    event_type,
+                    source,
+                    id,
+                    time,
+                    self.subject,
+                    self.schemaurl,
+                    self.datacontenttype,
+                    self.datacontentencoding,
+                    self.data,
+                    self.extensions,
+                ))
+            }
+            _ => Err(violations.into_errors()),
+        }
+    }
+}
+
+impl EventSerializer<CloudEventV0_3> for CloudEventV0_3Builder {
+    fn set_spec_version(self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V0_3, version)?;
+        Ok(self)
+    }
+
+    fn set_attribute(self, name: &str, value: String) -> Result<Self, Error> {
+        Ok(match name {
+            "type" => self.event_type(value),
+            "source" => self.source(value),
+            "id" => self.event_id(value),
+            "time" => self.time(value),
+            "subject" => self.subject(value),
+            "schemaurl" => self.schemaurl(value),
+            "datacontenttype" => self.datacontenttype(value),
+            "datacontentencoding" => self.datacontentencoding(value),
+            _ => return Err(Error::UnknownAttribute(name.to_owned())),
+        })
+    }
+
+    fn set_extension(self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        Ok(self.extension(name, value))
+    }
+
+    fn end_with_data(self, data: Data) -> Result<CloudEventV0_3, Error> {
+        self.data(data).build()
+    }
+
+    fn end(self) -> Result<CloudEventV0_3, Error> {
+        self.build()
+    }
+}
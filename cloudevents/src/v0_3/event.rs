@@ -0,0 +1,358 @@
+use crate::event::duplicate_attribute;
+use crate::message::{end_with_extensions, format_time, EventDeserializer, EventSerializer};
+use crate::validation::{self, Violations};
+use crate::Data;
+use crate::ExtensionValue;
+use crate::{Error, SpecVersion, Validate};
+use chrono::prelude::{DateTime, FixedOffset};
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, MapAccess, Unexpected, Visitor,
+};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// CloudEvent according to spec version 0.3
+///
+/// In the JSON format, extension attributes are serialized as top-level members next to
+/// the context attributes. When deserializing, every unknown top-level member is collected
+/// as an extension. An event created with [`Data::Binary`] gets a `datacontentencoding` of
+/// `base64`, unless another one is given, so the data is serialized as base64 encoded `data`
+/// and decoded back when deserializing. With formats which are not human readable, such as
+/// CBOR, it is serialized as a byte string instead.
+///
+/// [`Data::Binary`]: ../enum.Data.html#variant.Binary
+#[derive(Debug, PartialEq, Clone)]
+pub struct CloudEventV0_3 {
+    pub(crate) event_type: String,
+    pub(crate) specversion: String,
+    pub(crate) source: String,
+    pub(crate) id: String,
+    pub(crate) time: Option<DateTime<FixedOffset>>,
+    pub(crate) subject: Option<String>,
+    pub(crate) schemaurl: Option<String>,
+    pub(crate) datacontenttype: Option<String>,
+    pub(crate) datacontentencoding: Option<String>,
+    pub(crate) data: Option<Data>,
+    pub(crate) extensions: Option<HashMap<String, ExtensionValue>>,
+}
+
+impl CloudEventV0_3 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        event_type: String,
+        source: String,
+        id: String,
+        time: Option<DateTime<FixedOffset>>,
+        subject: Option<String>,
+        schemaurl: Option<String>,
+        datacontenttype: Option<String>,
+        datacontentencoding: Option<String>,
+        data: Option<Data>,
+        extensions: Option<HashMap<String, ExtensionValue>>,
+    ) -> Self {
+        let datacontentencoding = match (datacontentencoding, &data) {
+            (None, Some(Data::Binary(_))) => Some(String::from("base64")),
+            (datacontentencoding, _) => datacontentencoding,
+        };
+        CloudEventV0_3 {
+            event_type,
+            specversion: String::from("0.3"),
+            source,
+            id,
+            time,
+            subject,
+            schemaurl,
+            datacontenttype,
+            datacontentencoding,
+            data,
+            extensions,
+        }
+    }
+
+    /// Get the event type
+    pub fn event_type(&self) -> &str {
+        self.event_type.as_ref()
+    }
+
+    /// Get the source
+    pub fn source(&self) -> &str {
+        self.source.as_ref()
+    }
+
+    /// Get the event id
+    pub fn event_id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// Get the event time
+    pub fn event_time(&self) -> Option<&DateTime<FixedOffset>> {
+        self.time.as_ref()
+    }
+
+    /// Get the subject
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_ref().map(|x| x.as_ref())
+    }
+
+    /// Get the schemaurl
+    pub fn schema_url(&self) -> Option<&str> {
+        self.schemaurl.as_ref().map(|x| x.as_ref())
+    }
+
+    /// Get the datacontenttype
+    pub fn datacontenttype(&self) -> Option<&str> {
+        self.datacontenttype.as_ref().map(|x| x.as_ref())
+    }
+
+    /// Get the datacontentencoding
+    pub fn datacontentencoding(&self) -> Option<&str> {
+        self.datacontentencoding.as_ref().map(|x| x.as_ref())
+    }
+
+    /// Get the data
+    pub fn data(&self) -> Option<&Data> {
+        self.data.as_ref()
+    }
+
+    /// Decode the data into `T` according to its content type.
+    ///
+    /// The data is decoded according to the `datacontenttype` attribute:
+    ///
+    /// * Without a content type, or with `application/json` or a `+json` suffix, the data is
+    ///   parsed as JSON, whether it is held as a JSON value, a string or binary data.
+    /// * With a `text/*` content type, the data is read as a string, binary data being decoded
+    ///   as UTF-8.
+    /// * String data with a `datacontentencoding` of `base64` is decoded to binary data first.
+    /// * With any other content type, binary data is read as a sequence of bytes, e.g. into
+    ///   a `Vec<u8>`.
+    ///
+    /// Returns `Ok(None)` if the event carries no data.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the content type is not supported for the data, e.g. string
+    /// data with an `image/png` content type, or if the data cannot be decoded into `T`.
+    pub fn data_as<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.data
+            .as_ref()
+            .map(|data| data.decode(self.datacontenttype(), self.datacontentencoding()))
+            .transpose()
+    }
+
+    /// Get the extensions
+    pub fn extensions(&self) -> Option<&HashMap<String, ExtensionValue>> {
+        self.extensions.as_ref()
+    }
+}
+
+impl Validate for CloudEventV0_3 {
+    fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut violations = Violations::default();
+        validation::context(
+            &mut violations,
+            Some(&self.event_type),
+            Some(&self.source),
+            Some(&self.id),
+        );
+        validate_optional(
+            &mut violations,
+            self.subject.as_deref(),
+            self.schemaurl.as_deref(),
+            self.datacontenttype.as_deref(),
+            self.datacontentencoding.as_deref(),
+            self.extensions.as_ref(),
+        );
+        violations.into_result()
+    }
+}
+
+/// Validate the optional attributes and the extensions according to spec version 0.3.
+fn validate_optional(
+    violations: &mut Violations,
+    subject: Option<&str>,
+    schemaurl: Option<&str>,
+    datacontenttype: Option<&str>,
+    datacontentencoding: Option<&str>,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    if let Some(subject) = subject {
+        violations.check(validation::non_empty("subject", subject));
+    }
+    if let Some(schemaurl) = schemaurl {
+        violations.check(validation::uri("schemaurl", schemaurl));
+    }
+    if let Some(datacontenttype) = datacontenttype {
+        violations.check(validation::media_type("datacontenttype", datacontenttype));
+    }
+    if let Some(datacontentencoding) = datacontentencoding {
+        violations.check(validation::content_encoding(
+            "datacontentencoding",
+            datacontentencoding,
+        ));
+    }
+    validation::extension_names(violations, extensions);
+}
+
+impl EventDeserializer for CloudEventV0_3 {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let mut s = serializer
+            .set_spec_version(SpecVersion::V0_3)?
+            .set_attribute("type", self.event_type)?
+            .set_attribute("source", self.source)?
+            .set_attribute("id", self.id)?;
+        if let Some(time) = self.time {
+            s = s.set_attribute("time", format_time(&time))?;
+        }
+        if let Some(subject) = self.subject {
+            s = s.set_attribute("subject", subject)?;
+        }
+        if let Some(schemaurl) = self.schemaurl {
+            s = s.set_attribute("schemaurl", schemaurl)?;
+        }
+        if let Some(datacontenttype) = self.datacontenttype {
+            s = s.set_attribute("datacontenttype", datacontenttype)?;
+        }
+        // Binary data is handed over decoded, so its encoding does not apply anymore
+        if let Some(datacontentencoding) = self.datacontentencoding {
+            if !matches!(self.data, Some(Data::Binary(_))) {
+                s = s.set_attribute("datacontentencoding", datacontentencoding)?;
+            }
+        }
+        end_with_extensions(s, self.extensions, self.data)
+    }
+}
+
+impl Serialize for CloudEventV0_3 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.event_type)?;
+        map.serialize_entry("specversion", &self.specversion)?;
+        map.serialize_entry("source", &self.source)?;
+        map.serialize_entry("id", &self.id)?;
+        if let Some(time) = &self.time {
+            map.serialize_entry("time", time)?;
+        }
+        if let Some(subject) = &self.subject {
+            map.serialize_entry("subject", subject)?;
+        }
+        if let Some(schemaurl) = &self.schemaurl {
+            map.serialize_entry("schemaurl", schemaurl)?;
+        }
+        if let Some(datacontenttype) = &self.datacontenttype {
+            map.serialize_entry("datacontenttype", datacontenttype)?;
+        }
+        if let Some(datacontentencoding) = &self.datacontentencoding {
+            map.serialize_entry("datacontentencoding", datacontentencoding)?;
+        }
+        if let Some(data) = &self.data {
+            map.serialize_entry("data", data)?;
+        }
+        if let Some(extensions) = &self.extensions {
+            for (name, value) in extensions {
+                map.serialize_entry(name, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CloudEventV0_3 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(CloudEventV0_3Visitor)
+    }
+}
+
+struct CloudEventV0_3Visitor;
+
+impl<'de> Visitor<'de> for CloudEventV0_3Visitor {
+    type Value = CloudEventV0_3;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent according to spec version 0.3")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut event_type = None;
+        let mut specversion: Option<String> = None;
+        let mut source = None;
+        let mut id = None;
+        let mut time = None;
+        let mut subject = None;
+        let mut schemaurl = None;
+        let mut datacontenttype = None;
+        let mut datacontentencoding: Option<String> = None;
+        let mut data = None;
+        let mut extensions = HashMap::new();
+        let mut names = HashSet::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            if !names.insert(key.clone()) {
+                return Err(duplicate_attribute(&key));
+            }
+            match key.as_str() {
+                "type" => event_type = Some(map.next_value()?),
+                "specversion" => specversion = Some(map.next_value()?),
+                "source" => source = Some(map.next_value()?),
+                "id" => id = Some(map.next_value()?),
+                "time" => time = map.next_value()?,
+                "subject" => subject = map.next_value()?,
+                "schemaurl" => schemaurl = map.next_value()?,
+                "datacontenttype" => datacontenttype = map.next_value()?,
+                "datacontentencoding" => datacontentencoding = map.next_value()?,
+                "data" => data = map.next_value()?,
+                _ => {
+                    extensions.insert(key, map.next_value()?);
+                }
+            }
+        }
+
+        let specversion = specversion.ok_or_else(|| de::Error::missing_field("specversion"))?;
+        if specversion != "0.3" {
+            return Err(de::Error::invalid_value(
+                Unexpected::Str(&specversion),
+                &"0.3",
+            ));
+        }
+
+        let base64 = matches!(&datacontentencoding, Some(e) if e.eq_ignore_ascii_case("base64"));
+        if let (true, Some(Data::StringOrBinary(encoded))) = (base64, &data) {
+            let bytes = base64::decode(encoded).map_err(|e| {
+                de::Error::custom(format_args!(
+                    "invalid base64 encoded attribute `data`: {}",
+                    e
+                ))
+            })?;
+            data = Some(Data::Binary(bytes));
+        }
+
+        Ok(CloudEventV0_3::new(
+            event_type.ok_or_else(|| de::Error::missing_field("type"))?,
+            source.ok_or_else(|| de::Error::missing_field("source"))?,
+            id.ok_or_else(|| de::Error::missing_field("id"))?,
+            time,
+            subject,
+            schemaurl,
+            datacontenttype,
+            datacontentencoding,
+            data,
+            if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        ))
+    }
+}
@@ -0,0 +1,44 @@
+/*!
+
+# Macro Usage
+
+```
+use cloudevents::cloudevent_v0_3;
+use cloudevents::{Data, CloudEventBuilder};
+use cloudevents::v0_3::CloudEventV0_3;
+use cloudevents::Error;
+
+let event : Result<CloudEventV0_3, Error> = cloudevent_v0_3!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+    time: "2019-12-04T18:33:09+00:00",
+    subject: "me",
+    schemaurl: "https://lol.org/schema.json"
+    datacontenttype: "application/json",
+    data: Data::from_string("\"test\""),
+);
+```
+
+# Builder Usage
+
+```
+use cloudevents::{Data, CloudEventBuilder};
+use cloudevents::v0_3::CloudEventV0_3;
+use cloudevents::Error;
+
+let event : Result<CloudEventV0_3, Error> = CloudEventBuilder::v0_3()
+  .event_id("id")
+  .source("http://www.google.com")
+  .event_type("test type")
+  .datacontenttype("application/json")
+  .data(Data::from_string("\"test\""))
+  .build();
+```
+ */
+mod builder;
+mod event;
+mod helper;
+
+pub use self::builder::CloudEventV0_3Builder;
+pub use self::event::CloudEventV0_3;
@@ -0,0 +1,351 @@
+use super::CloudEventV1_0;
+use crate::common::Payload;
+use crate::event::duplicate_attribute;
+use crate::{Data, Error, ExtensionValue};
+use chrono::prelude::{DateTime, FixedOffset};
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, MapAccess, Unexpected, Visitor,
+};
+use serde_json::value::RawValue;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::convert::TryFrom;
+use std::fmt;
+
+/// CloudEvent according to spec version 1.0, borrowing its attributes and data from the
+/// JSON it is deserialized from.
+///
+/// Deserializing a [`CloudEventV1_0`] allocates every attribute, extension and the data.
+/// A `CloudEventRef` instead borrows string attributes, which are only allocated when they
+/// contain escape sequences, and keeps the data and the extension values as raw JSON,
+/// which are only parsed when the event is converted into a [`CloudEventV1_0`] with
+/// [`TryFrom`], or for the data when it is decoded with [`data_as`]. It can only be
+/// deserialized from JSON, with `serde_json::from_str` or `serde_json::from_slice`.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::v1_0::{CloudEventRef, CloudEventV1_0, DataRef};
+/// use cloudevents::Data;
+/// use std::convert::TryFrom;
+///
+/// let json = r#"{
+///     "specversion": "1.0",
+///     "type": "test type",
+///     "source": "http://www.google.com",
+///     "id": "id",
+///     "data": {"hello": "world"}
+/// }"#;
+///
+/// let event: CloudEventRef = serde_json::from_str(json).unwrap();
+/// assert_eq!(event.event_type(), "test type");
+/// match event.data() {
+///     Some(DataRef::Json(raw)) => assert_eq!(raw.get(), r#"{"hello": "world"}"#),
+///     _ => unreachable!(),
+/// }
+///
+/// let event = CloudEventV1_0::try_from(event).unwrap();
+/// assert_eq!(
+///     event.data(),
+///     Some(&Data::from_serializable(serde_json::json!({"hello": "world"})).unwrap())
+/// );
+/// ```
+///
+/// [`CloudEventV1_0`]: struct.CloudEventV1_0.html
+/// [`TryFrom`]: https://doc.rust-lang.org/std/convert/trait.TryFrom.html
+/// [`data_as`]: #method.data_as
+#[derive(Debug, Clone)]
+pub struct CloudEventRef<'a> {
+    event_type: Cow<'a, str>,
+    source: Cow<'a, str>,
+    id: Cow<'a, str>,
+    time: Option<DateTime<FixedOffset>>,
+    subject: Option<Cow<'a, str>>,
+    dataschema: Option<Cow<'a, str>>,
+    datacontenttype: Option<Cow<'a, str>>,
+    data: Option<DataRef<'a>>,
+    extensions: Vec<(Cow<'a, str>, &'a RawValue)>,
+}
+
+/// Data of a [`CloudEventRef`], borrowed from the JSON it is deserialized from.
+///
+/// [`CloudEventRef`]: struct.CloudEventRef.html
+#[derive(Debug, Clone)]
+pub enum DataRef<'a> {
+    /// The `data` member, as raw JSON.
+    Json(&'a RawValue),
+    /// The `data_base64` member, still base64 encoded.
+    Base64(Cow<'a, str>),
+}
+
+impl<'a> CloudEventRef<'a> {
+    /// Get the event type
+    pub fn event_type(&self) -> &str {
+        self.event_type.as_ref()
+    }
+
+    /// Get the source
+    pub fn source(&self) -> &str {
+        self.source.as_ref()
+    }
+
+    /// Get the event id
+    pub fn event_id(&self) -> &str {
+        self.id.as_ref()
+    }
+
+    /// Get the event time
+    pub fn event_time(&self) -> Option<&DateTime<FixedOffset>> {
+        self.time.as_ref()
+    }
+
+    /// Get the subject
+    pub fn subject(&self) -> Option<&str> {
+        self.subject.as_deref()
+    }
+
+    /// Get the dataschema
+    pub fn dataschema(&self) -> Option<&str> {
+        self.dataschema.as_deref()
+    }
+
+    /// Get the datacontenttype
+    pub fn datacontenttype(&self) -> Option<&str> {
+        self.datacontenttype.as_deref()
+    }
+
+    /// Get the data
+    pub fn data(&self) -> Option<&DataRef<'a>> {
+        self.data.as_ref()
+    }
+
+    /// Decode the data into `T` according to the datacontenttype, straight from the raw JSON.
+    ///
+    /// The result is the same as calling `data_as` on the [`CloudEventV1_0`] this event
+    /// converts to, without parsing the data into a [`Data`] first.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the content type is not supported for the data, e.g. string
+    /// data with an `image/png` content type, or if the data cannot be decoded into `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::v1_0::CloudEventRef;
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    ///
+    /// let json = r#"{
+    ///     "specversion": "1.0",
+    ///     "type": "test type",
+    ///     "source": "http://www.google.com",
+    ///     "id": "id",
+    ///     "data": {"id": 1}
+    /// }"#;
+    ///
+    /// let event: CloudEventRef = serde_json::from_str(json).unwrap();
+    /// assert_eq!(event.data_as::<Order>().unwrap(), Some(Order { id: 1 }));
+    /// ```
+    ///
+    /// [`CloudEventV1_0`]: struct.CloudEventV1_0.html
+    /// [`Data`]: ../enum.Data.html
+    pub fn data_as<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        let text;
+        let payload = match &self.data {
+            Some(DataRef::Json(raw)) if raw.get().starts_with('"') => {
+                text = serde_json::from_str::<BorrowedStr>(raw.get())?.0;
+                Payload::Text(&text)
+            }
+            Some(DataRef::Json(raw)) => Payload::Json(raw),
+            Some(DataRef::Base64(encoded)) => Payload::Bytes(Cow::Owned(
+                base64::decode(encoded.as_ref()).map_err(|source| Error::InvalidBase64 {
+                    attribute: "data_base64",
+                    source,
+                })?,
+            )),
+            None => return Ok(None),
+        };
+        payload.decode(self.datacontenttype()).map(Some)
+    }
+
+    /// Get the extensions, with their values as raw JSON, in the order they are read.
+    pub fn extensions(&self) -> impl Iterator<Item = (&str, &'a RawValue)> {
+        self.extensions
+            .iter()
+            .map(|(name, value)| (name.as_ref(), *value))
+    }
+}
+
+impl<'a> TryFrom<CloudEventRef<'a>> for CloudEventV1_0 {
+    type Error = Error;
+
+    /// Parse the data and the extensions, and take ownership of the attributes.
+    ///
+    /// The result is the same as deserializing the JSON into a [`CloudEventV1_0`] directly.
+    ///
+    /// [`CloudEventV1_0`]: struct.CloudEventV1_0.html
+    fn try_from(event: CloudEventRef<'a>) -> Result<Self, Error> {
+        let data = match event.data {
+            Some(DataRef::Json(raw)) => Some(serde_json::from_str::<Data>(raw.get())?),
+            Some(DataRef::Base64(encoded)) => Some(Data::Binary(
+                base64::decode(encoded.as_ref()).map_err(|source| Error::InvalidBase64 {
+                    attribute: "data_base64",
+                    source,
+                })?,
+            )),
+            None => None,
+        };
+        let mut extensions = HashMap::new();
+        for (name, raw) in event.extensions {
+            let value: ExtensionValue = serde_json::from_str(raw.get())?;
+            extensions.insert(name.into_owned(), value);
+        }
+
+        Ok(CloudEventV1_0::new(
+            event.event_type.into_owned(),
+            event.source.into_owned(),
+            event.id.into_owned(),
+            event.time,
+            event.subject.map(Cow::into_owned),
+            event.dataschema.map(Cow::into_owned),
+            event.datacontenttype.map(Cow::into_owned),
+            data,
+            if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        ))
+    }
+}
+
+impl<'de> Deserialize<'de> for CloudEventRef<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(CloudEventRefVisitor)
+    }
+}
+
+struct CloudEventRefVisitor;
+
+impl<'de> Visitor<'de> for CloudEventRefVisitor {
+    type Value = CloudEventRef<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent according to spec version 1.0")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut event_type = None;
+        let mut specversion = None;
+        let mut source = None;
+        let mut id = None;
+        let mut time = None;
+        let mut subject = None;
+        let mut dataschema = None;
+        let mut datacontenttype = None;
+        let mut data = None;
+        let mut data_base64 = None;
+        let mut extensions = Vec::new();
+        let mut names = HashSet::new();
+
+        while let Some(BorrowedStr(key)) = map.next_key()? {
+            if !names.insert(key.clone()) {
+                return Err(duplicate_attribute(&key));
+            }
+            match key.as_ref() {
+                "type" => event_type = Some(map.next_value::<BorrowedStr>()?.0),
+                "specversion" => specversion = Some(map.next_value::<BorrowedStr>()?.0),
+                "source" => source = Some(map.next_value::<BorrowedStr>()?.0),
+                "id" => id = Some(map.next_value::<BorrowedStr>()?.0),
+                "time" => time = map.next_value()?,
+                "subject" => subject = optional(map.next_value()?),
+                "dataschema" => dataschema = optional(map.next_value()?),
+                "datacontenttype" => datacontenttype = optional(map.next_value()?),
+                "data" => data = map.next_value::<Option<&RawValue>>()?.map(DataRef::Json),
+                "data_base64" => data_base64 = optional(map.next_value()?),
+                _ => extensions.push((key, map.next_value()?)),
+            }
+        }
+
+        let specversion = specversion.ok_or_else(|| de::Error::missing_field("specversion"))?;
+        if specversion != "1.0" {
+            return Err(de::Error::invalid_value(
+                Unexpected::Str(&specversion),
+                &"1.0",
+            ));
+        }
+
+        if let Some(encoded) = data_base64 {
+            if data.is_some() {
+                return Err(de::Error::custom(
+                    "`data` and `data_base64` are mutually exclusive",
+                ));
+            }
+            data = Some(DataRef::Base64(encoded));
+        }
+
+        Ok(CloudEventRef {
+            event_type: event_type.ok_or_else(|| de::Error::missing_field("type"))?,
+            source: source.ok_or_else(|| de::Error::missing_field("source"))?,
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            time,
+            subject,
+            dataschema,
+            datacontenttype,
+            data,
+            extensions,
+        })
+    }
+}
+
+fn optional(value: Option<BorrowedStr>) -> Option<Cow<str>> {
+    value.map(|s| s.0)
+}
+
+/// A string borrowed from the input, unless it has to be unescaped.
+struct BorrowedStr<'a>(Cow<'a, str>);
+
+impl<'de> Deserialize<'de> for BorrowedStr<'de> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(BorrowedStrVisitor)
+    }
+}
+
+struct BorrowedStrVisitor;
+
+impl<'de> Visitor<'de> for BorrowedStrVisitor {
+    type Value = BorrowedStr<'de>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string")
+    }
+
+    fn visit_borrowed_str<E>(self, v: &'de str) -> Result<Self::Value, E> {
+        Ok(BorrowedStr(Cow::Borrowed(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E> {
+        Ok(BorrowedStr(Cow::Owned(v.to_owned())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Self::Value, E> {
+        Ok(BorrowedStr(Cow::Owned(v)))
+    }
+}
@@ -1,10 +1,12 @@
 use super::CloudEventV1_0;
+use crate::message::{expect_spec_version, EventSerializer};
+use crate::validation::{self, Violations};
 use crate::Data;
+use crate::Error;
 use crate::ExtensionValue;
-use chrono::prelude::{DateTime, FixedOffset, Local};
-use failure::{format_err, Error};
+use crate::SpecVersion;
+use serde::Serialize;
 use std::collections::HashMap;
-use url::{ParseError, Url};
 
 /// Create a new [`CloudEvent`] according to spec version 0.2.
 ///
@@ -12,7 +14,7 @@ use url::{ParseError, Url};
 ///
 /// ```
 /// use cloudevents::v1_0::{CloudEventV1_0,CloudEventV1_0Builder};
-/// use failure::Error;
+/// use cloudevents::Error;
 ///
 /// let event : Result<CloudEventV1_0, Error> = CloudEventV1_0Builder::default()
 ///     .event_id("id")
@@ -23,7 +25,7 @@ use url::{ParseError, Url};
 /// ```
 ///
 /// [`CloudEvent`]: struct.CloudEventV1_0.html
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct CloudEventV1_0Builder {
     event_type: Option<String>,
     source: Option<String>,
@@ -85,72 +87,221 @@ impl CloudEventV1_0Builder {
         self
     }
 
+    /// Set JSON data, serialized from a [`Serialize`] value, and set the datacontenttype
+    /// to `application/json`.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the value cannot be serialized to JSON.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::v1_0::CloudEventV1_0Builder;
+    /// use serde_json::json;
+    ///
+    /// let event = CloudEventV1_0Builder::default()
+    ///     .event_id("id")
+    ///     .source("http://www.google.com")
+    ///     .event_type("test type")
+    ///     .json_data(json!({"id": 1}))
+    ///     .unwrap()
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(event.datacontenttype(), Some("application/json"));
+    /// ```
+    ///
+    /// [`Serialize`]: https://docs.serde.rs/serde/ser/trait.Serialize.html
+    pub fn json_data<T: Serialize>(self, value: T) -> Result<Self, Error> {
+        Ok(self
+            .data(Data::from_serializable(value)?)
+            .datacontenttype("application/json"))
+    }
+
+    /// Set string data, and set the datacontenttype to the given media type, e.g. `text/plain`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::v1_0::CloudEventV1_0Builder;
+    /// use cloudevents::Data;
+    ///
+    /// let event = CloudEventV1_0Builder::default()
+    ///     .event_id("id")
+    ///     .source("http://www.google.com")
+    ///     .event_type("test type")
+    ///     .text_data("hello", "text/plain")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(event.data(), Some(&Data::from_string("hello")));
+    /// assert_eq!(event.datacontenttype(), Some("text/plain"));
+    /// ```
+    pub fn text_data<S: Into<String>, M: Into<String>>(self, s: S, media_type: M) -> Self {
+        self.data(Data::from_string(s)).datacontenttype(media_type)
+    }
+
+    /// Set binary data, and set the datacontenttype to the given media type, e.g. `image/png`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::v1_0::CloudEventV1_0Builder;
+    /// use cloudevents::Data;
+    ///
+    /// let event = CloudEventV1_0Builder::default()
+    ///     .event_id("id")
+    ///     .source("http://www.google.com")
+    ///     .event_type("test type")
+    ///     .binary_data(b"\x89PNG", "image/png")
+    ///     .build()
+    ///     .unwrap();
+    ///
+    /// assert_eq!(event.data(), Some(&Data::from_binary(b"\x89PNG")));
+    /// assert_eq!(event.datacontenttype(), Some("image/png"));
+    /// ```
+    pub fn binary_data<B: AsRef<[u8]>, M: Into<String>>(self, bytes: B, media_type: M) -> Self {
+        self.data(Data::from_binary(bytes))
+            .datacontenttype(media_type)
+    }
+
     /// Set the extensions.
     pub fn extensions(mut self, e: HashMap<String, ExtensionValue>) -> Self {
         self.extensions = Some(e);
         self
     }
 
+    /// Add an extension.
+    pub fn extension<S: Into<String>, V: Into<ExtensionValue>>(
+        mut self,
+        name: S,
+        value: V,
+    ) -> Self {
+        self.extensions
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
     /// Build a [`CloudEvent`].
     ///
     /// # Errors
     ///
     /// An error is thrown if one of the required fields (event_type, id or source) is not populated,
-    /// or if one of the validated fields (time, source and schemeurl) are populated with an invalid value.
+    /// if one of the validated fields (time, source and dataschema) is populated with an invalid value,
+    /// if an extension is a nested JSON object or array, or if the datacontenttype contradicts
+    /// the data, e.g. JSON data set with [`Data::from_serializable`] and a `text/plain` datacontenttype.
+    /// The remaining rules of the spec are checked by [`Validate`].
+    /// Only the first error is returned, use [`build_collecting_errors`] to get all of them.
     ///
-    /// [`CloudEvent`]: struct.CloudEvent.html
+    /// [`CloudEvent`]: struct.CloudEventV1_0.html
+    /// [`build_collecting_errors`]: #method.build_collecting_errors
+    /// [`Validate`]: ../trait.Validate.html
+    /// [`Data::from_serializable`]: ../enum.Data.html#method.from_serializable
     pub fn build(self) -> Result<CloudEventV1_0, Error> {
-        Ok(CloudEventV1_0::new(
-            self.event_type
-                .ok_or(format_err!("Event type is required"))?,
-            {
-                if let Some(x) = self.source {
-                    let source = x;
-                    match Url::parse(&source) {
-                        Ok(_) | Err(ParseError::RelativeUrlWithoutBase) => source,
-                        Err(e) => return Err(format_err!("{}", e)),
-                    }
-                } else {
-                    return Err(format_err!("Source is required"));
-                }
-            },
-            self.id.ok_or(format_err!("Event id is required"))?,
-            {
-                match self.time.as_ref() {
-                    Some(t) if t == "now" => Some(DateTime::<FixedOffset>::from(Local::now())),
-                    Some(t) => Some(DateTime::parse_from_rfc3339(&t)?),
-                    None => None,
-                }
-            },
-            self.subject,
-            {
-                match self.dataschema {
-                    Some(dataschema) => match Url::parse(&dataschema) {
-                        Ok(_) | Err(ParseError::RelativeUrlWithoutBase) => Some(dataschema),
-                        Err(e) => return Err(format_err!("{}", e)),
-                    },
-                    None => None,
-                }
-            },
-            self.datacontenttype,
-            self.data,
-            self.extensions,
-        ))
+        self.build_collecting_errors()
+            .map_err(|mut errors| errors.remove(0))
     }
-}
 
-impl Default for CloudEventV1_0Builder {
-    fn default() -> Self {
-        CloudEventV1_0Builder {
-            event_type: None,
-            source: None,
-            id: None,
-            time: None,
-            subject: None,
-            dataschema: None,
-            datacontenttype: None,
-            data: None,
-            extensions: None,
+    /// Build a [`CloudEvent`], validating every attribute instead of stopping at the first error.
+    ///
+    /// # Errors
+    ///
+    /// The errors of all missing or invalid attributes are returned, in the order the
+    /// attributes are defined by the spec.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::CloudEventBuilder;
+    ///
+    /// let errors = CloudEventBuilder::v1_0()
+    ///     .source("http://www.google.com:port")
+    ///     .time("yesterday")
+    ///     .build_collecting_errors()
+    ///     .unwrap_err();
+    ///
+    /// let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    /// assert_eq!(attributes, vec!["type", "source", "id", "time"]);
+    /// ```
+    ///
+    /// [`CloudEvent`]: struct.CloudEventV1_0.html
+    pub fn build_collecting_errors(self) -> Result<CloudEventV1_0, Vec<Error>> {
+        let mut violations = Violations::default();
+        violations.check(validation::required("type", self.event_type.as_ref()));
+        violations.check(
+            validation::required("source", self.source.as_deref())
+                .and_then(|source| validation::uri_ref("source", source)),
+        );
+        violations.check(validation::required("id", self.id.as_ref()));
+        let time = violations
+            .check(
+                self.time
+                    .as_deref()
+                    .map(validation::time_or_now)
+                    .transpose(),
+            )
+            .flatten();
+        if let Some(dataschema) = &self.dataschema {
+            violations.check(validation::uri_ref("dataschema", dataschema));
+        }
+        if let (Some(datacontenttype), Some(data)) = (&self.datacontenttype, &self.data) {
+            violations.check(validation::data_content_type(
+                "datacontenttype",
+                datacontenttype,
+                data,
+            ));
+        }
+        validation::extension_values_not_nested(&mut violations, self.extensions.as_ref());
+
+        match (self.event_type, self.source, self.id) {
+            (Some(event_type), Some(source), Some(id)) if violations.is_empty() => {
+                Ok(CloudEventV1_0::new(
+                    event_type,
+                    source,
+                    id,
+                    time,
+                    self.subject,
+                    self.dataschema,
+                    self.datacontenttype,
+                    self.data,
+                    self.extensions,
+                ))
+            }
+            _ => Err(violations.into_errors()),
         }
     }
 }
+
+impl EventSerializer<CloudEventV1_0> for CloudEventV1_0Builder {
+    fn set_spec_version(self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V1_0, version)?;
+        Ok(self)
+    }
+
+    fn set_attribute(self, name: &str, value: String) -> Result<Self, Error> {
+        Ok(match name {
+            "type" => self.event_type(value),
+            "source" => self.source(value),
+            "id" => self.event_id(value),
+            "time" => self.time(value),
+            "subject" => self.subject(value),
+            "dataschema" => self.dataschema(value),
+            "datacontenttype" => self.datacontenttype(value),
+            _ => return Err(Error::UnknownAttribute(name.to_owned())),
+        })
+    }
+
+    fn set_extension(self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        Ok(self.extension(name, value))
+    }
+
+    fn end_with_data(self, data: Data) -> Result<CloudEventV1_0, Error> {
+        self.data(data).build()
+    }
+
+    fn end(self) -> Result<CloudEventV1_0, Error> {
+        self.build()
+    }
+}
@@ -1,38 +1,41 @@
+use crate::event::duplicate_attribute;
+use crate::message::{end_with_extensions, format_time, EventDeserializer, EventSerializer};
+use crate::validation::{self, Violations};
 use crate::Data;
 use crate::ExtensionValue;
+use crate::{Error, SpecVersion, Validate};
 use chrono::prelude::{DateTime, FixedOffset};
-use serde_derive::{Deserialize, Serialize};
-use std::collections::HashMap;
+use serde::de::{
+    self, Deserialize, DeserializeOwned, Deserializer, MapAccess, Unexpected, Visitor,
+};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 
 /// CloudEvent according to spec version 1.0
-#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+///
+/// In the JSON format, extension attributes are serialized as top-level members next to
+/// the context attributes. When deserializing, every unknown top-level member is collected
+/// as an extension. [`Data::Binary`] is serialized as base64 encoded `data_base64`, or as a
+/// byte string in `data` with formats which are not human readable, such as CBOR.
+///
+/// [`Data::Binary`]: ../enum.Data.html#variant.Binary
+#[derive(Debug, PartialEq, Clone)]
 pub struct CloudEventV1_0 {
-    #[serde(rename = "type")]
-    event_type: String,
-    specversion: String,
-    source: String,
-    id: String,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    time: Option<DateTime<FixedOffset>>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    subject: Option<String>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    dataschema: Option<String>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    datacontenttype: Option<String>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    data: Option<Data>,
-    #[serde(default)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    extensions: Option<HashMap<String, ExtensionValue>>,
+    pub(crate) event_type: String,
+    pub(crate) specversion: String,
+    pub(crate) source: String,
+    pub(crate) id: String,
+    pub(crate) time: Option<DateTime<FixedOffset>>,
+    pub(crate) subject: Option<String>,
+    pub(crate) dataschema: Option<String>,
+    pub(crate) datacontenttype: Option<String>,
+    pub(crate) data: Option<Data>,
+    pub(crate) extensions: Option<HashMap<String, ExtensionValue>>,
 }
 
 impl CloudEventV1_0 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         event_type: String,
         source: String,
@@ -97,8 +100,268 @@ impl CloudEventV1_0 {
         self.data.as_ref()
     }
 
+    /// Decode the data into `T` according to its content type.
+    ///
+    /// The data is decoded according to the `datacontenttype` attribute:
+    ///
+    /// * Without a content type, or with `application/json` or a `+json` suffix, the data is
+    ///   parsed as JSON, whether it is held as a JSON value, a string or binary data.
+    /// * With a `text/*` content type, the data is read as a string, binary data being decoded
+    ///   as UTF-8.
+    /// * With any other content type, binary data is read as a sequence of bytes, e.g. into
+    ///   a `Vec<u8>`.
+    ///
+    /// Returns `Ok(None)` if the event carries no data.
+    ///
+    /// # Errors
+    ///
+    /// An error is returned if the content type is not supported for the data, e.g. string
+    /// data with an `image/png` content type, or if the data cannot be decoded into `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::cloudevent_v1_0;
+    /// use cloudevents::Data;
+    /// use serde_derive::Deserialize;
+    ///
+    /// #[derive(Debug, PartialEq, Deserialize)]
+    /// struct Order {
+    ///     id: u32,
+    /// }
+    ///
+    /// let event = cloudevent_v1_0!(
+    ///     event_type: "test type",
+    ///     source: "http://www.google.com",
+    ///     event_id: "id",
+    ///     datacontenttype: "application/json",
+    ///     data: Data::from_string(r#"{"id": 1}"#),
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(event.data_as::<Order>().unwrap(), Some(Order { id: 1 }));
+    /// ```
+    pub fn data_as<T>(&self) -> Result<Option<T>, Error>
+    where
+        T: DeserializeOwned,
+    {
+        self.data
+            .as_ref()
+            .map(|data| data.decode(self.datacontenttype(), None))
+            .transpose()
+    }
+
     /// Get the extensions
     pub fn extensions(&self) -> Option<&HashMap<String, ExtensionValue>> {
         self.extensions.as_ref()
     }
 }
+
+impl Validate for CloudEventV1_0 {
+    fn validate(&self) -> Result<(), Vec<Error>> {
+        let mut violations = Violations::default();
+        validation::context(
+            &mut violations,
+            Some(&self.event_type),
+            Some(&self.source),
+            Some(&self.id),
+        );
+        validate_optional(
+            &mut violations,
+            self.subject.as_deref(),
+            self.dataschema.as_deref(),
+            self.datacontenttype.as_deref(),
+            self.data.as_ref(),
+            self.extensions.as_ref(),
+        );
+        violations.into_result()
+    }
+}
+
+/// Validate the optional attributes, the data and the extensions according to spec version 1.0.
+fn validate_optional(
+    violations: &mut Violations,
+    subject: Option<&str>,
+    dataschema: Option<&str>,
+    datacontenttype: Option<&str>,
+    data: Option<&Data>,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    if let Some(subject) = subject {
+        violations.check(validation::non_empty("subject", subject));
+    }
+    if let Some(dataschema) = dataschema {
+        violations.check(validation::uri("dataschema", dataschema));
+    }
+    if let Some(datacontenttype) = datacontenttype {
+        let valid = violations
+            .check(validation::media_type("datacontenttype", datacontenttype))
+            .is_some();
+        if let (true, Some(data)) = (valid, data) {
+            violations.check(validation::data_content_type(
+                "datacontenttype",
+                datacontenttype,
+                data,
+            ));
+        }
+    }
+    validation::extension_names(violations, extensions);
+    validation::extension_values_v1_0(violations, extensions);
+}
+
+impl EventDeserializer for CloudEventV1_0 {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let mut s = serializer
+            .set_spec_version(SpecVersion::V1_0)?
+            .set_attribute("type", self.event_type)?
+            .set_attribute("source", self.source)?
+            .set_attribute("id", self.id)?;
+        if let Some(time) = self.time {
+            s = s.set_attribute("time", format_time(&time))?;
+        }
+        if let Some(subject) = self.subject {
+            s = s.set_attribute("subject", subject)?;
+        }
+        if let Some(dataschema) = self.dataschema {
+            s = s.set_attribute("dataschema", dataschema)?;
+        }
+        if let Some(datacontenttype) = self.datacontenttype {
+            s = s.set_attribute("datacontenttype", datacontenttype)?;
+        }
+        end_with_extensions(s, self.extensions, self.data)
+    }
+}
+
+impl Serialize for CloudEventV1_0 {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let human_readable = serializer.is_human_readable();
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("type", &self.event_type)?;
+        map.serialize_entry("specversion", &self.specversion)?;
+        map.serialize_entry("source", &self.source)?;
+        map.serialize_entry("id", &self.id)?;
+        if let Some(time) = &self.time {
+            map.serialize_entry("time", time)?;
+        }
+        if let Some(subject) = &self.subject {
+            map.serialize_entry("subject", subject)?;
+        }
+        if let Some(dataschema) = &self.dataschema {
+            map.serialize_entry("dataschema", dataschema)?;
+        }
+        if let Some(datacontenttype) = &self.datacontenttype {
+            map.serialize_entry("datacontenttype", datacontenttype)?;
+        }
+        match &self.data {
+            Some(Data::Binary(bytes)) if human_readable => {
+                map.serialize_entry("data_base64", &base64::encode(bytes))?
+            }
+            Some(data) => map.serialize_entry("data", data)?,
+            None => (),
+        }
+        if let Some(extensions) = &self.extensions {
+            for (name, value) in extensions {
+                map.serialize_entry(name, value)?;
+            }
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for CloudEventV1_0 {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_map(CloudEventV1_0Visitor)
+    }
+}
+
+struct CloudEventV1_0Visitor;
+
+impl<'de> Visitor<'de> for CloudEventV1_0Visitor {
+    type Value = CloudEventV1_0;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a CloudEvent according to spec version 1.0")
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut event_type = None;
+        let mut specversion: Option<String> = None;
+        let mut source = None;
+        let mut id = None;
+        let mut time = None;
+        let mut subject = None;
+        let mut dataschema = None;
+        let mut datacontenttype = None;
+        let mut data = None;
+        let mut data_base64: Option<String> = None;
+        let mut extensions = HashMap::new();
+        let mut names = HashSet::new();
+
+        while let Some(key) = map.next_key::<String>()? {
+            if !names.insert(key.clone()) {
+                return Err(duplicate_attribute(&key));
+            }
+            match key.as_str() {
+                "type" => event_type = Some(map.next_value()?),
+                "specversion" => specversion = Some(map.next_value()?),
+                "source" => source = Some(map.next_value()?),
+                "id" => id = Some(map.next_value()?),
+                "time" => time = map.next_value()?,
+                "subject" => subject = map.next_value()?,
+                "dataschema" => dataschema = map.next_value()?,
+                "datacontenttype" => datacontenttype = map.next_value()?,
+                "data" => data = map.next_value()?,
+                "data_base64" => data_base64 = map.next_value()?,
+                _ => {
+                    extensions.insert(key, map.next_value()?);
+                }
+            }
+        }
+
+        let specversion = specversion.ok_or_else(|| de::Error::missing_field("specversion"))?;
+        if specversion != "1.0" {
+            return Err(de::Error::invalid_value(
+                Unexpected::Str(&specversion),
+                &"1.0",
+            ));
+        }
+
+        if let Some(encoded) = data_base64 {
+            if data.is_some() {
+                return Err(de::Error::custom(
+                    "`data` and `data_base64` are mutually exclusive",
+                ));
+            }
+            let bytes = base64::decode(&encoded).map_err(|e| {
+                de::Error::custom(format_args!("invalid attribute `data_base64`: {}", e))
+            })?;
+            data = Some(Data::Binary(bytes));
+        }
+
+        Ok(CloudEventV1_0 {
+            event_type: event_type.ok_or_else(|| de::Error::missing_field("type"))?,
+            specversion,
+            source: source.ok_or_else(|| de::Error::missing_field("source"))?,
+            id: id.ok_or_else(|| de::Error::missing_field("id"))?,
+            time,
+            subject,
+            dataschema,
+            datacontenttype,
+            data,
+            extensions: if extensions.is_empty() {
+                None
+            } else {
+                Some(extensions)
+            },
+        })
+    }
+}
@@ -6,7 +6,7 @@
 use cloudevents::cloudevent_v1_0;
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::v1_0::CloudEventV1_0;
-use failure::Error;
+use cloudevents::Error;
 
 let event : Result<CloudEventV1_0, Error> = cloudevent_v1_0!(
     event_type: "test type",
@@ -25,7 +25,7 @@ let event : Result<CloudEventV1_0, Error> = cloudevent_v1_0!(
 ```
 use cloudevents::{Data, CloudEventBuilder};
 use cloudevents::v1_0::CloudEventV1_0;
-use failure::Error;
+use cloudevents::Error;
 
 let event : Result<CloudEventV1_0, Error> = CloudEventBuilder::v1_0()
   .event_id("id")
@@ -34,9 +34,11 @@ let event : Result<CloudEventV1_0, Error> = CloudEventBuilder::v1_0()
   .build();
 ```
  */
+mod borrowed;
 mod builder;
 mod event;
 mod helper;
 
+pub use self::borrowed::{CloudEventRef, DataRef};
 pub use self::builder::CloudEventV1_0Builder;
 pub use self::event::CloudEventV1_0;
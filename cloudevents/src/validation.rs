@@ -0,0 +1,320 @@
+use crate::message::{essence, is_json};
+use crate::{Data, Error, ExtensionValue};
+use chrono::prelude::{DateTime, FixedOffset, Local};
+use serde::de::{self, Deserialize, Deserializer};
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Deref;
+use url::{ParseError, Url};
+
+/// Check an event against the requirements of its spec version.
+///
+/// Deserializing an event only checks that the attributes have the right type. Use
+/// [`validate`] to check every other requirement of the spec, or deserialize into
+/// [`Validated`] to do both at once.
+///
+/// [`validate`]: #tymethod.validate
+/// [`Validated`]: struct.Validated.html
+pub trait Validate {
+    /// Validate the event.
+    ///
+    /// # Errors
+    ///
+    /// The errors of all attributes violating the spec are returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use cloudevents::{CloudEvent, Validate};
+    /// use serde_json::json;
+    ///
+    /// let event: CloudEvent = serde_json::from_value(json!({
+    ///     "type": "test type",
+    ///     "specversion": "1.0",
+    ///     "source": "http://www.google.com",
+    ///     "id": "",
+    ///     "dataschema": "/schema.json",
+    /// })).unwrap();
+    ///
+    /// let errors = event.validate().unwrap_err();
+    /// let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    /// assert_eq!(attributes, vec!["id", "dataschema"]);
+    /// ```
+    fn validate(&self) -> Result<(), Vec<Error>>;
+}
+
+/// Deserializes an event and validates it against its spec version.
+///
+/// # Example
+///
+/// ```
+/// use cloudevents::{CloudEvent, Validated};
+/// use serde_json::json;
+///
+/// let result = serde_json::from_value::<Validated<CloudEvent>>(json!({
+///     "type": "test type",
+///     "specversion": "1.0",
+///     "source": "http://www.google.com",
+///     "id": "",
+/// }));
+/// assert!(result.is_err());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Validated<T>(T);
+
+impl<T> Validated<T> {
+    /// Get the validated event.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Validated<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<'de, T> Deserialize<'de> for Validated<T>
+where
+    T: Deserialize<'de> + Validate,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let event = T::deserialize(deserializer)?;
+        event
+            .validate()
+            .map_err(|errors| de::Error::custom(JoinedErrors(&errors)))?;
+        Ok(Validated(event))
+    }
+}
+
+struct JoinedErrors<'a>(&'a [Error]);
+
+impl<'a> fmt::Display for JoinedErrors<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, error) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the errors of every validated attribute, instead of stopping at the first one.
+#[derive(Debug, Default)]
+pub(crate) struct Violations(Vec<Error>);
+
+impl Violations {
+    /// Record the error of a failed validation, returning the validated value otherwise.
+    pub(crate) fn check<T>(&mut self, result: Result<T, Error>) -> Option<T> {
+        match result {
+            Ok(v) => Some(v),
+            Err(e) => {
+                self.0.push(e);
+                None
+            }
+        }
+    }
+
+    pub(crate) fn push(&mut self, error: Error) {
+        self.0.push(error);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn into_errors(self) -> Vec<Error> {
+        self.0
+    }
+
+    pub(crate) fn into_result(self) -> Result<(), Vec<Error>> {
+        if self.0.is_empty() {
+            Ok(())
+        } else {
+            Err(self.0)
+        }
+    }
+}
+
+/// Validate the required attributes shared by all spec versions.
+pub(crate) fn context(
+    violations: &mut Violations,
+    event_type: Option<&str>,
+    source: Option<&str>,
+    id: Option<&str>,
+) {
+    violations.check(required("type", event_type).and_then(|t| non_empty("type", t)));
+    violations.check(
+        required("source", source)
+            .and_then(|s| non_empty("source", s).and_then(|_| uri_ref("source", s))),
+    );
+    violations.check(required("id", id).and_then(|i| non_empty("id", i)));
+}
+
+/// Ensure a required attribute is populated.
+pub(crate) fn required<T>(attribute: &'static str, value: Option<T>) -> Result<T, Error> {
+    value.ok_or(Error::MissingRequiredAttribute(attribute))
+}
+
+/// Ensure a string attribute is not empty.
+pub(crate) fn non_empty(attribute: &'static str, value: &str) -> Result<(), Error> {
+    if value.is_empty() {
+        Err(Error::EmptyAttribute(attribute))
+    } else {
+        Ok(())
+    }
+}
+
+/// Ensure the attribute is either an absolute URI or a relative reference.
+pub(crate) fn uri_ref(attribute: &'static str, value: &str) -> Result<(), Error> {
+    match Url::parse(value) {
+        Ok(_) | Err(ParseError::RelativeUrlWithoutBase) => Ok(()),
+        Err(source) => Err(Error::InvalidUri { attribute, source }),
+    }
+}
+
+/// Ensure the attribute is an absolute URI.
+pub(crate) fn uri(attribute: &'static str, value: &str) -> Result<(), Error> {
+    non_empty(attribute, value)?;
+    Url::parse(value)
+        .map(|_| ())
+        .map_err(|source| Error::InvalidUri { attribute, source })
+}
+
+/// Ensure the attribute is a media type according to RFC 2046, e.g. `text/plain; charset=utf-8`.
+pub(crate) fn media_type(attribute: &'static str, value: &str) -> Result<(), Error> {
+    let is_token = |s: &str| {
+        !s.is_empty()
+            && s.chars()
+                .all(|c| c.is_ascii_graphic() && !"()<>@,;:\\\"/[]?=".contains(c))
+    };
+    let mut parts = value.split(';');
+    let essence = parts.next().unwrap_or_default().trim();
+    let valid_essence = match essence.find('/') {
+        Some(i) => is_token(&essence[..i]) && is_token(&essence[i + 1..]),
+        None => false,
+    };
+    let valid_parameters = parts.all(|parameter| match parameter.find('=') {
+        Some(i) => is_token(parameter[..i].trim()) && !parameter[i + 1..].trim().is_empty(),
+        None => false,
+    });
+    if valid_essence && valid_parameters {
+        Ok(())
+    } else {
+        Err(Error::InvalidMediaType {
+            attribute,
+            value: value.to_owned(),
+        })
+    }
+}
+
+/// Ensure the content type attribute does not contradict the data: JSON values can only be
+/// carried with a JSON content type, `application/json` or a `+json` suffix.
+pub(crate) fn data_content_type(
+    attribute: &'static str,
+    value: &str,
+    data: &Data,
+) -> Result<(), Error> {
+    match data {
+        Data::Object(_) if !is_json(&essence(value)) => Err(Error::DataContentTypeMismatch {
+            attribute,
+            value: value.to_owned(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Ensure the attribute is a content transfer encoding according to RFC 2045, section 6.1.
+pub(crate) fn content_encoding(attribute: &'static str, value: &str) -> Result<(), Error> {
+    let encoding = value.to_ascii_lowercase();
+    match encoding.as_str() {
+        "7bit" | "8bit" | "binary" | "quoted-printable" | "base64" => Ok(()),
+        e if e.starts_with("x-") && e.len() > 2 => Ok(()),
+        _ => Err(Error::InvalidContentEncoding {
+            attribute,
+            value: value.to_owned(),
+        }),
+    }
+}
+
+/// Parse a RFC 3339 timestamp.
+pub(crate) fn time(value: &str) -> Result<DateTime<FixedOffset>, Error> {
+    DateTime::parse_from_rfc3339(value).map_err(|source| Error::InvalidTime { source })
+}
+
+/// Parse a RFC 3339 timestamp, or take the current time if the value is `now`.
+pub(crate) fn time_or_now(value: &str) -> Result<DateTime<FixedOffset>, Error> {
+    if value == "now" {
+        Ok(DateTime::<FixedOffset>::from(Local::now()))
+    } else {
+        time(value)
+    }
+}
+
+/// Ensure the extension names consist of at most 20 lower-case letters and digits.
+pub(crate) fn extension_names(
+    violations: &mut Violations,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    for name in sorted_names(extensions) {
+        let valid = !name.is_empty()
+            && name.len() <= 20
+            && name
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit());
+        if !valid {
+            violations.push(Error::InvalidExtensionName(name.to_owned()));
+        }
+    }
+}
+
+/// Ensure the extension values are part of the type system of spec version 1.0.
+pub(crate) fn extension_values_v1_0(
+    violations: &mut Violations,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    let extensions = match extensions {
+        Some(extensions) => extensions,
+        None => return,
+    };
+    for name in sorted_names(Some(extensions)) {
+        if !extensions[name].is_in_type_system_v1_0() {
+            violations.push(Error::InvalidExtensionValue(name.to_owned()));
+        }
+    }
+}
+
+/// Ensure the extension values are not nested JSON objects or arrays.
+pub(crate) fn extension_values_not_nested(
+    violations: &mut Violations,
+    extensions: Option<&HashMap<String, ExtensionValue>>,
+) {
+    let extensions = match extensions {
+        Some(extensions) => extensions,
+        None => return,
+    };
+    for name in sorted_names(Some(extensions)) {
+        if extensions[name].is_nested() {
+            violations.push(Error::InvalidExtensionValue(name.to_owned()));
+        }
+    }
+}
+
+/// Extension names in a stable order, so errors are reported deterministically.
+fn sorted_names(extensions: Option<&HashMap<String, ExtensionValue>>) -> Vec<&str> {
+    let mut names: Vec<&str> = extensions
+        .into_iter()
+        .flat_map(|e| e.keys())
+        .map(String::as_str)
+        .collect();
+    names.sort_unstable();
+    names
+}
@@ -0,0 +1,86 @@
+/*!
+WebSocket protocol binding.
+
+Implements the WebSocket protocol binding (see [WebSockets Protocol Binding](https://github.com/cloudevents/spec/blob/main/cloudevents/bindings/websockets-protocol-binding.md))
+for the `cloudevents.json` subprotocol, on top of [`WebSocketFrame`], a client independent
+view of a WebSocket data message. Every message carries a single event in the JSON format.
+
+The subprotocol is negotiated during the opening handshake: the client lists the subprotocols it supports
+in the `Sec-WebSocket-Protocol` header, and the server answers with the one it selected,
+see [`negotiate_subprotocol`].
+
+# Example
+
+```
+use cloudevents::websocket::{from_frame, negotiate_subprotocol, to_frame, WebSocketFrame};
+use cloudevents::{cloudevent, CloudEvent};
+
+assert_eq!(
+    negotiate_subprotocol("cloudevents.avro, cloudevents.json"),
+    Some("cloudevents.json")
+);
+
+let event: CloudEvent = cloudevent!(
+    event_type: "test type",
+    source: "http://www.google.com",
+    event_id: "id",
+).unwrap().into();
+
+let frame = to_frame(&event).unwrap();
+assert!(matches!(frame, WebSocketFrame::Text(_)));
+assert_eq!(from_frame(&frame).unwrap(), event);
+```
+
+[`WebSocketFrame`]: enum.WebSocketFrame.html
+[`negotiate_subprotocol`]: fn.negotiate_subprotocol.html
+*/
+use crate::{CloudEvent, Error};
+
+/// Name of the subprotocol carrying events in the JSON format.
+pub const SUBPROTOCOL_JSON: &str = "cloudevents.json";
+
+/// A WebSocket data message, independent of any client library.
+#[derive(Debug, PartialEq, Clone)]
+pub enum WebSocketFrame {
+    /// A text message.
+    Text(String),
+    /// A binary message.
+    Binary(Vec<u8>),
+}
+
+/// Select the subprotocol to use from the value of a `Sec-WebSocket-Protocol` header.
+///
+/// The header holds a comma separated list of subprotocols offered by the client.
+/// Returns [`SUBPROTOCOL_JSON`] if it is offered, or `None` if no supported subprotocol is.
+///
+/// [`SUBPROTOCOL_JSON`]: constant.SUBPROTOCOL_JSON.html
+pub fn negotiate_subprotocol(header: &str) -> Option<&'static str> {
+    header
+        .split(',')
+        .map(str::trim)
+        .find(|protocol| *protocol == SUBPROTOCOL_JSON)
+        .map(|_| SUBPROTOCOL_JSON)
+}
+
+/// Frame the event as a text message in the JSON format.
+///
+/// # Errors
+///
+/// An error is returned if the event cannot be serialized.
+pub fn to_frame(event: &CloudEvent) -> Result<WebSocketFrame, Error> {
+    Ok(WebSocketFrame::Text(serde_json::to_string(event)?))
+}
+
+/// Read the event carried by a message in the JSON format.
+///
+/// Text messages are expected, but binary messages holding UTF-8 encoded JSON are read as well.
+///
+/// # Errors
+///
+/// An error is returned if the message does not carry a valid event.
+pub fn from_frame(frame: &WebSocketFrame) -> Result<CloudEvent, Error> {
+    Ok(match frame {
+        WebSocketFrame::Text(text) => serde_json::from_str(text)?,
+        WebSocketFrame::Binary(bytes) => serde_json::from_slice(bytes)?,
+    })
+}
@@ -0,0 +1,406 @@
+/*!
+XML event format, available with the `xml` feature.
+
+Implements the XML event format (see [XML Format for CloudEvents](https://github.com/cloudevents/spec/blob/main/cloudevents/working-drafts/xml-format.md))
+for events of spec version 1.0, use [`CloudEvent::to_version`] to convert events of other spec versions.
+
+The event is an `event` element of the `http://cloudevents.io/xmlformat/V1` namespace, bound to the `ce`
+prefix, with the spec version as `specversion` attribute:
+
+* Context attributes are mapped to elements of the same name, holding their string representation.
+* Extensions are mapped to elements of the same name, typed with `xsi:type`: `xs:boolean`, `xs:int`,
+  `xs:string`, `xs:base64Binary`, `xs:anyURI` for URIs and URI-references, and `xs:dateTime`.
+  When reading, an absolute `xs:anyURI` is read as a URI and any other as a URI-reference.
+* Binary data is carried base64 encoded in `dataBinary`. String data with an XML content type which holds
+  a single element is embedded in `dataXml`, without its XML declaration, any other string and JSON data
+  is carried in `dataText`. When reading `dataText`, data with a JSON content type is parsed as JSON.
+
+# Example
+
+```
+use cloudevents::xml::{from_str, to_string};
+use cloudevents::v1_0::CloudEventV1_0Builder;
+use cloudevents::Data;
+
+let event = CloudEventV1_0Builder::default()
+    .event_type("test type")
+    .source("http://www.google.com")
+    .event_id("id")
+    .extension("count", 42)
+    .datacontenttype("application/xml")
+    .data(Data::from_string("<order id=\"1\"/>"))
+    .build()
+    .unwrap();
+
+let xml = to_string(&event).unwrap();
+assert!(xml.contains(r#"<ce:count xsi:type="xs:int">42</ce:count>"#));
+assert!(xml.contains(r#"<ce:dataXml><order id="1"/></ce:dataXml>"#));
+assert_eq!(from_str(&xml).unwrap(), event);
+```
+
+[`CloudEvent::to_version`]: ../enum.CloudEvent.html#method.to_version
+*/
+use crate::message::{
+    essence, expect_spec_version, format_time, is_json, EventDeserializer, EventSerializer,
+};
+use crate::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use crate::{Data, Error, ExtensionValue, SpecVersion};
+use chrono::prelude::DateTime;
+use quick_xml::escape::escape;
+use quick_xml::events::{BytesStart, BytesText, Event};
+use quick_xml::name::{Namespace, ResolveResult};
+use quick_xml::reader::{NsReader, Reader};
+use std::convert::TryFrom;
+use url::Url;
+
+/// Content type of a structured mode message in the XML format.
+pub const CLOUDEVENTS_XML: &str = "application/cloudevents+xml";
+
+/// Namespace of the elements of the XML format.
+pub const NAMESPACE: &str = "http://cloudevents.io/xmlformat/V1";
+
+const XS_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema";
+const XSI_NAMESPACE: &str = "http://www.w3.org/2001/XMLSchema-instance";
+
+/// Serialize the event in the XML format.
+///
+/// # Errors
+///
+/// An error is returned if an extension cannot be represented in the format, e.g. an integer
+/// out of the 32 bits range or a name which is not a valid XML name, or if JSON data cannot
+/// be serialized.
+pub fn to_string(event: &CloudEventV1_0) -> Result<String, Error> {
+    event.clone().deserialize_event(XmlWriter::default())
+}
+
+/// Parse an event in the XML format.
+///
+/// # Errors
+///
+/// An error is returned if the input is not a well-formed document, or if it does not carry a valid event,
+/// e.g. if it has more than one data element.
+pub fn from_str(xml: &str) -> Result<CloudEventV1_0, Error> {
+    XmlReader(xml).deserialize_event(CloudEventV1_0Builder::default())
+}
+
+/// Writes an event to an XML document.
+#[derive(Default)]
+struct XmlWriter {
+    xml: String,
+    datacontenttype: Option<String>,
+}
+
+impl XmlWriter {
+    fn element(&mut self, name: &str, xsi_type: Option<&str>, content: &str) -> Result<(), Error> {
+        if !is_xml_name(name) {
+            return Err(Error::InvalidExtensionName(name.to_owned()));
+        }
+        self.xml.push_str("<ce:");
+        self.xml.push_str(name);
+        if let Some(xsi_type) = xsi_type {
+            self.xml.push_str(" xsi:type=\"xs:");
+            self.xml.push_str(xsi_type);
+            self.xml.push('"');
+        }
+        self.xml.push('>');
+        self.xml.push_str(&escape(content));
+        self.xml.push_str("</ce:");
+        self.xml.push_str(name);
+        self.xml.push('>');
+        Ok(())
+    }
+}
+
+impl EventSerializer<String> for XmlWriter {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        expect_spec_version(SpecVersion::V1_0, version)?;
+        self.xml = format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?><ce:event xmlns:ce="{}" xmlns:xs="{}" xmlns:xsi="{}" specversion="{}">"#,
+            NAMESPACE,
+            XS_NAMESPACE,
+            XSI_NAMESPACE,
+            version.as_str()
+        );
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        self.element(name, None, &value)?;
+        if name == "datacontenttype" {
+            self.datacontenttype = Some(value);
+        }
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        let (xsi_type, content) = match value {
+            ExtensionValue::Boolean(b) => ("boolean", b.to_string()),
+            ExtensionValue::Integer(i) => (
+                "int",
+                i32::try_from(i)
+                    .map_err(|_| Error::InvalidExtensionValue(name.to_owned()))?
+                    .to_string(),
+            ),
+            ExtensionValue::String(s) => ("string", s),
+            ExtensionValue::Binary(b) => ("base64Binary", base64::encode(&b)),
+            ExtensionValue::Uri(u) => ("anyURI", u.as_str().to_owned()),
+            ExtensionValue::UriRef(u) => ("anyURI", u),
+            ExtensionValue::Timestamp(t) => ("dateTime", format_time(&t)),
+            ExtensionValue::Object(_) => return Err(Error::InvalidExtensionValue(name.to_owned())),
+        };
+        self.element(name, Some(xsi_type), &content)?;
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<String, Error> {
+        match data {
+            Data::Binary(bytes) => self.element("dataBinary", None, &base64::encode(&bytes))?,
+            Data::StringOrBinary(text) => {
+                let is_xml =
+                    matches!(self.datacontenttype.as_deref().map(essence), Some(t) if is_xml(&t));
+                match document_element(&text) {
+                    Some(element) if is_xml => {
+                        self.xml.push_str("<ce:dataXml>");
+                        self.xml.push_str(element);
+                        self.xml.push_str("</ce:dataXml>");
+                    }
+                    _ => self.element("dataText", None, &text)?,
+                }
+            }
+            Data::Object(value) => {
+                self.element("dataText", None, &serde_json::to_string(&value)?)?;
+            }
+        }
+        self.end()
+    }
+
+    fn end(mut self) -> Result<String, Error> {
+        self.xml.push_str("</ce:event>");
+        Ok(self.xml)
+    }
+}
+
+/// Reads an event from an XML document.
+struct XmlReader<'a>(&'a str);
+
+/// Data elements, read before the content type is known.
+enum XmlData {
+    Text(String),
+    Binary(Vec<u8>),
+    Xml(String),
+}
+
+impl<'a> EventDeserializer for XmlReader<'a> {
+    fn deserialize_event<R, S: EventSerializer<R>>(self, serializer: S) -> Result<R, Error> {
+        let mut reader = NsReader::from_str(self.0);
+        reader.expand_empty_elements(true);
+
+        let root = loop {
+            match reader.read_resolved_event()? {
+                (ns, Event::Start(e)) if is_ce(&ns) && e.local_name().as_ref() == b"event" => {
+                    break e
+                }
+                (_, Event::Text(t)) if is_blank(&t) => {}
+                (_, Event::Decl(_)) | (_, Event::Comment(_)) | (_, Event::PI(_)) => {}
+                (_, Event::DocType(_)) => {}
+                _ => return Err(malformed("expected an event element")),
+            }
+        };
+        let spec_version: SpecVersion = match root.try_get_attribute("specversion")? {
+            Some(attribute) => attribute.unescape_value()?.parse()?,
+            None => return Err(Error::MissingRequiredAttribute("specversion")),
+        };
+        let names = spec_version.attribute_names();
+
+        let mut serializer = serializer.set_spec_version(spec_version)?;
+        let mut datacontenttype = None;
+        let mut data = None;
+        loop {
+            let element = match reader.read_resolved_event()? {
+                (ns, Event::Start(e)) if is_ce(&ns) => e,
+                (_, Event::Start(_)) => return Err(malformed("unexpected element")),
+                (_, Event::End(_)) => break,
+                (_, Event::Text(t)) if is_blank(&t) => continue,
+                (_, Event::Comment(_)) | (_, Event::PI(_)) => continue,
+                (_, Event::Eof) => return Err(malformed("unexpected end of document")),
+                _ => return Err(malformed("unexpected content in the event element")),
+            };
+            let name = reader
+                .decoder()
+                .decode(element.local_name().as_ref())?
+                .into_owned();
+            let is_data = matches!(name.as_str(), "dataXml" | "dataText" | "dataBinary");
+            if is_data && data.is_some() {
+                return Err(malformed("duplicate data element"));
+            }
+            match name.as_str() {
+                "dataXml" => {
+                    let content = reader.read_text(element.name())?;
+                    data = Some(XmlData::Xml(content.trim().to_owned()));
+                }
+                "dataText" => data = Some(XmlData::Text(text(&mut reader)?)),
+                "dataBinary" => {
+                    let bytes = base64::decode(text(&mut reader)?.trim()).map_err(|source| {
+                        Error::InvalidBase64 {
+                            attribute: "dataBinary",
+                            source,
+                        }
+                    })?;
+                    data = Some(XmlData::Binary(bytes));
+                }
+                _ if names.contains(&name.as_str()) => {
+                    let value = text(&mut reader)?;
+                    if name == spec_version.content_type_attribute() {
+                        datacontenttype = Some(value.clone());
+                    }
+                    serializer = serializer.set_attribute(&name, value)?;
+                }
+                _ => {
+                    let xsi_type = xsi_type(&reader, &element)?;
+                    let value = extension_value(&name, xsi_type.as_deref(), text(&mut reader)?)?;
+                    serializer = serializer.set_extension(&name, value)?;
+                }
+            }
+        }
+
+        loop {
+            match reader.read_event()? {
+                Event::Eof => break,
+                Event::Text(t) if is_blank(&t) => {}
+                Event::Comment(_) | Event::PI(_) => {}
+                _ => return Err(malformed("unexpected content after the event element")),
+            }
+        }
+
+        let data = match data {
+            Some(XmlData::Text(text)) => match datacontenttype.as_deref().map(essence) {
+                Some(t) if is_json(&t) => Data::Object(serde_json::from_str(&text)?),
+                _ => Data::StringOrBinary(text),
+            },
+            Some(XmlData::Binary(bytes)) => Data::Binary(bytes),
+            Some(XmlData::Xml(xml)) => Data::StringOrBinary(xml),
+            None => return serializer.end(),
+        };
+        serializer.end_with_data(data)
+    }
+}
+
+fn malformed(reason: &'static str) -> Error {
+    Error::InvalidXmlDocument(reason)
+}
+
+fn is_ce(ns: &ResolveResult) -> bool {
+    matches!(ns, ResolveResult::Bound(Namespace(ns)) if *ns == NAMESPACE.as_bytes())
+}
+
+fn is_blank(text: &BytesText) -> bool {
+    text.iter().all(u8::is_ascii_whitespace)
+}
+
+/// Whether a name can be used as the local part of an element name, i.e. is an XML `NCName`.
+fn is_xml_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-' || c == '.')
+}
+
+/// Whether a media type, without parameters, denotes XML content.
+fn is_xml(media_type: &str) -> bool {
+    media_type == "application/xml" || media_type == "text/xml" || media_type.ends_with("+xml")
+}
+
+/// Read the text content of an element, up to its end tag.
+fn text(reader: &mut NsReader<&[u8]>) -> Result<String, Error> {
+    let mut text = String::new();
+    loop {
+        match reader.read_event()? {
+            Event::Text(t) => text.push_str(&t.unescape()?),
+            Event::CData(c) => text.push_str(&reader.decoder().decode(&c)?),
+            Event::Comment(_) => {}
+            Event::End(_) => return Ok(text),
+            Event::Eof => return Err(malformed("unexpected end of document")),
+            _ => return Err(malformed("expected text content")),
+        }
+    }
+}
+
+/// Get the local part of the `xsi:type` attribute of an element.
+fn xsi_type(reader: &NsReader<&[u8]>, element: &BytesStart) -> Result<Option<String>, Error> {
+    for attribute in element.attributes() {
+        let attribute = attribute.map_err(quick_xml::Error::from)?;
+        let (ns, local_name) = reader.resolve_attribute(attribute.key);
+        if matches!(ns, ResolveResult::Bound(Namespace(ns)) if ns == XSI_NAMESPACE.as_bytes())
+            && local_name.as_ref() == b"type"
+        {
+            let value = attribute.unescape_value()?;
+            let local = value.rsplit(':').next().unwrap_or_default();
+            return Ok(Some(local.to_owned()));
+        }
+    }
+    Ok(None)
+}
+
+fn extension_value(
+    name: &str,
+    xsi_type: Option<&str>,
+    text: String,
+) -> Result<ExtensionValue, Error> {
+    let invalid = || Error::InvalidAttributeValue(name.to_owned());
+    Ok(match xsi_type {
+        None | Some("string") => ExtensionValue::String(text),
+        Some("boolean") => match text.trim() {
+            "true" | "1" => ExtensionValue::Boolean(true),
+            "false" | "0" => ExtensionValue::Boolean(false),
+            _ => return Err(invalid()),
+        },
+        Some("int") => ExtensionValue::Integer(i64::from(
+            text.trim().parse::<i32>().map_err(|_| invalid())?,
+        )),
+        Some("base64Binary") => {
+            ExtensionValue::Binary(base64::decode(text.trim()).map_err(|_| invalid())?)
+        }
+        Some("anyURI") => match Url::parse(&text) {
+            Ok(url) => ExtensionValue::Uri(url),
+            Err(_) => ExtensionValue::UriRef(text),
+        },
+        Some("dateTime") => ExtensionValue::Timestamp(
+            DateTime::parse_from_rfc3339(text.trim()).map_err(|_| invalid())?,
+        ),
+        Some(_) => return Err(invalid()),
+    })
+}
+
+/// Get the document element of an XML document, if it is well-formed and only preceded
+/// by an XML declaration.
+fn document_element(document: &str) -> Option<&str> {
+    let mut reader = Reader::from_str(document);
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut end = None;
+    loop {
+        let position = reader.buffer_position();
+        match reader.read_event().ok()? {
+            Event::Decl(_) if start.is_none() => {}
+            Event::Text(t) if depth == 0 && is_blank(&t) => {}
+            Event::Start(_) | Event::Empty(_) if depth == 0 && start.is_some() => return None,
+            Event::Start(_) => {
+                start.get_or_insert(position);
+                depth += 1;
+            }
+            Event::Empty(_) if depth == 0 => {
+                start = Some(position);
+                end = Some(reader.buffer_position());
+            }
+            Event::End(_) => {
+                depth = depth.checked_sub(1)?;
+                if depth == 0 {
+                    end = Some(reader.buffer_position());
+                }
+            }
+            Event::Eof => break,
+            _ if depth > 0 => {}
+            _ => return None,
+        }
+    }
+    document.get(start?..end?)
+}
@@ -0,0 +1,254 @@
+mod common;
+
+use cloudevents::amqp::{
+    from_message, to_binary_message, to_structured_message, AmqpMessage, AmqpValue,
+};
+use cloudevents::{cloudevent_v0_2, cloudevent_v1_0};
+use cloudevents::{CloudEvent, Data, Error, ExtensionValue};
+use common::v1_0_event;
+use serde_json::json;
+use std::collections::HashMap;
+
+/// Binary mode message of `v1_0_event`.
+const V1_0_FIXTURE: &[&[u8]] = &[
+    // properties: list8 of 25 bytes with 7 fields, content-type is a sym8
+    b"\x00\x53\x73\xc0\x19\x07\x40\x40\x40\x40\x40\x40",
+    b"\xa3\x10application/json",
+    // application-properties: map8 of 211 bytes with 14 items
+    b"\x00\x53\x74\xc1\xd3\x0e",
+    b"\xa1\x17cloudEvents:specversion\xa1\x031.0",
+    b"\xa1\x10cloudEvents:type\xa1\x09test type",
+    b"\xa1\x12cloudEvents:source\xa1\x15http://www.google.com",
+    b"\xa1\x0ecloudEvents:id\xa1\x02id",
+    b"\xa1\x10cloudEvents:time\x83\x00\x00\x01\x6e\xd2\x2f\xf6\x88",
+    b"\xa1\x13cloudEvents:subject\xa1\x02me",
+    b"\xa1\x13cloudEvents:comment\xa1\x0dh\xc3\xa4llo w\xc3\xb6rld",
+    // data: vbin8 of 15 bytes
+    b"\x00\x53\x75\xa0\x0f{\"key\":\"value\"}",
+];
+
+fn v0_2_event() -> CloudEvent {
+    let mut extensions = HashMap::new();
+    extensions.insert("num".to_owned(), ExtensionValue::from(42));
+    cloudevent_v0_2!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        contenttype: "application/json",
+        data: Data::from_serializable(json!({"a": 1})).unwrap(),
+        extensions: extensions,
+    )
+    .unwrap()
+    .into()
+}
+
+/// Binary mode message of `v0_2_event`.
+const V0_2_FIXTURE: &[&[u8]] = &[
+    // properties: list8 of 25 bytes with 7 fields, content-type is a sym8
+    b"\x00\x53\x73\xc0\x19\x07\x40\x40\x40\x40\x40\x40",
+    b"\xa3\x10application/json",
+    // application-properties: map8 of 121 bytes with 10 items
+    b"\x00\x53\x74\xc1\x79\x0a",
+    b"\xa1\x17cloudEvents:specversion\xa1\x030.2",
+    b"\xa1\x10cloudEvents:type\xa1\x01t",
+    b"\xa1\x12cloudEvents:source\xa1\x02/s",
+    b"\xa1\x0ecloudEvents:id\xa1\x011",
+    b"\xa1\x0fcloudEvents:num\x81\x00\x00\x00\x00\x00\x00\x00\x2a",
+    // data: vbin8 of 7 bytes
+    b"\x00\x53\x75\xa0\x07{\"a\":1}",
+];
+
+#[test]
+fn v1_0_binary_message_matches_fixture() {
+    let message = to_binary_message(&v1_0_event()).unwrap();
+
+    assert_eq!(message.to_bytes(), V1_0_FIXTURE.concat());
+}
+
+#[test]
+fn v1_0_fixture_is_decoded() {
+    let message = AmqpMessage::from_bytes(&V1_0_FIXTURE.concat()).unwrap();
+
+    assert_eq!(from_message(&message).unwrap(), v1_0_event());
+}
+
+#[test]
+fn extensions_keep_their_type() {
+    let mut extensions = HashMap::new();
+    extensions.insert("boolean".to_owned(), ExtensionValue::from(true));
+    extensions.insert("integer".to_owned(), ExtensionValue::from(42));
+    extensions.insert("binary".to_owned(), ExtensionValue::from(&b"\x01"[..]));
+    let event: CloudEvent = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        extensions: extensions,
+    )
+    .unwrap()
+    .into();
+
+    let message = to_binary_message(&event).unwrap();
+    assert_eq!(
+        message.property("cloudEvents:boolean"),
+        Some(&AmqpValue::Boolean(true))
+    );
+    assert_eq!(
+        message.property("cloudEvents:integer"),
+        Some(&AmqpValue::Long(42))
+    );
+    assert_eq!(
+        message.property("cloudEvents:binary"),
+        Some(&AmqpValue::Binary(vec![1]))
+    );
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn v0_2_binary_message_matches_fixture() {
+    let message = to_binary_message(&v0_2_event()).unwrap();
+
+    assert_eq!(message.to_bytes(), V0_2_FIXTURE.concat());
+}
+
+#[test]
+fn v0_2_fixture_is_decoded() {
+    let message = AmqpMessage::from_bytes(&V0_2_FIXTURE.concat()).unwrap();
+
+    assert_eq!(from_message(&message).unwrap(), v0_2_event());
+}
+
+#[test]
+fn structured_message_round_trip() {
+    for event in &[v1_0_event(), v0_2_event()] {
+        let message = to_structured_message(event).unwrap();
+        assert_eq!(
+            message.content_type.as_deref(),
+            Some("application/cloudevents+json")
+        );
+        assert!(message.application_properties.is_empty());
+
+        let decoded = AmqpMessage::from_bytes(&message.to_bytes()).unwrap();
+        assert_eq!(decoded, message);
+        assert_eq!(&from_message(&decoded).unwrap(), event);
+    }
+}
+
+#[test]
+fn large_values_use_four_bytes_sizes() {
+    let message = AmqpMessage {
+        content_type: Some("text/plain".to_owned()),
+        application_properties: vec![("key".to_owned(), AmqpValue::String("v".repeat(300)))],
+        body: Some(vec![0; 70_000]),
+    };
+
+    let bytes = message.to_bytes();
+    assert!(bytes.windows(5).any(|w| w == b"\xb1\x00\x00\x01\x2c"));
+    assert_eq!(AmqpMessage::from_bytes(&bytes).unwrap(), message);
+}
+
+#[test]
+fn other_sections_and_types_are_read() {
+    let bytes = [
+        // header: list0
+        &b"\x00\x53\x70\x45"[..],
+        // message-annotations: map8 with a symbol key and a smalluint value
+        b"\x00\x53\x72\xc1\x06\x02\xa3\x01x\x52\x07",
+        // properties: message-id as ulong, then content-type as sym8
+        b"\x00\x53\x73\xc0\x14\x07\x80\x00\x00\x00\x00\x00\x00\x00\x01\x40\x40\x40\x40\x40",
+        b"\xa3\x03a/b",
+        // application-properties with a smallint and a uuid
+        b"\x00\x53\x74\xc1\x1a\x04\xa1\x01i\x54\xff\xa1\x01u\x98",
+        &[0x11; 16],
+        // two data sections
+        b"\x00\x53\x75\xa0\x02ab\x00\x53\x75\xa0\x01c",
+    ]
+    .concat();
+
+    let message = AmqpMessage::from_bytes(&bytes).unwrap();
+    assert_eq!(
+        message,
+        AmqpMessage {
+            content_type: Some("a/b".to_owned()),
+            application_properties: vec![
+                ("i".to_owned(), AmqpValue::Long(-1)),
+                ("u".to_owned(), AmqpValue::Uuid([0x11; 16])),
+            ],
+            body: Some(b"abc".to_vec()),
+        }
+    );
+}
+
+#[test]
+fn alternative_prefix_is_accepted() {
+    let message = AmqpMessage {
+        application_properties: vec![
+            ("cloudEvents_specversion".to_owned(), AmqpValue::from("1.0")),
+            ("cloudEvents_type".to_owned(), AmqpValue::from("t")),
+            ("cloudEvents_source".to_owned(), AmqpValue::from("/s")),
+            ("cloudEvents_id".to_owned(), AmqpValue::from("1")),
+        ],
+        ..AmqpMessage::default()
+    };
+
+    assert_eq!(
+        from_message(&message).unwrap().spec_version().as_str(),
+        "1.0"
+    );
+}
+
+#[test]
+fn truncated_message_is_rejected() {
+    let bytes = V1_0_FIXTURE.concat();
+
+    assert!(matches!(
+        AmqpMessage::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(Error::InvalidAmqpMessage(_))
+    ));
+}
+
+#[test]
+fn mistyped_attribute_is_rejected() {
+    let message = AmqpMessage {
+        application_properties: vec![
+            ("cloudEvents:specversion".to_owned(), AmqpValue::from("1.0")),
+            ("cloudEvents:id".to_owned(), AmqpValue::Long(1)),
+        ],
+        ..AmqpMessage::default()
+    };
+
+    assert!(matches!(
+        from_message(&message),
+        Err(Error::InvalidAttributeValue(ref name)) if name == "id"
+    ));
+}
+
+#[test]
+fn binary_mode_truncates_time_to_milliseconds() {
+    let event: CloudEvent = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        time: "2020-01-01T00:00:00.123456789Z",
+    )
+    .unwrap()
+    .into();
+
+    let message = to_binary_message(&event).unwrap();
+    assert_eq!(
+        message.property("cloudEvents:time"),
+        Some(&AmqpValue::Timestamp(1_577_836_800_123))
+    );
+
+    let truncated: CloudEvent = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        time: "2020-01-01T00:00:00.123Z",
+    )
+    .unwrap()
+    .into();
+    assert_eq!(from_message(&message).unwrap(), truncated);
+
+    let structured = to_structured_message(&event).unwrap();
+    assert_eq!(from_message(&structured).unwrap(), event);
+}
@@ -0,0 +1,243 @@
+use cloudevents::avro::{from_bytes, to_bytes, AvroAttribute, AvroData, AvroRecord, SCHEMA};
+use cloudevents::cloudevent_v1_0;
+use cloudevents::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use cloudevents::{Data, Error, ExtensionValue};
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use url::Url;
+
+fn text_event() -> CloudEventV1_0 {
+    let mut extensions = HashMap::new();
+    extensions.insert("binary".to_owned(), ExtensionValue::from(&b"\x01\x02"[..]));
+    extensions.insert("count".to_owned(), ExtensionValue::from(42));
+    extensions.insert("flag".to_owned(), ExtensionValue::from(true));
+    cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        datacontenttype: "text/plain",
+        data: Data::from_string("hi"),
+        extensions: extensions,
+    )
+    .unwrap()
+}
+
+/// Record of `text_event`.
+const TEXT_FIXTURE: &[&[u8]] = &[
+    // attribute: a block of 8 entries, string values are branch 3
+    b"\x10",
+    b"\x16specversion\x06\x061.0",
+    b"\x08type\x06\x02t",
+    b"\x0csource\x06\x04/s",
+    b"\x04id\x06\x021",
+    b"\x1edatacontenttype\x06\x14text/plain",
+    b"\x0cbinary\x08\x04\x01\x02",
+    b"\x0acount\x04\x54",
+    b"\x08flag\x02\x01",
+    b"\x00",
+    // data: string branch
+    b"\x0c\x04hi",
+];
+
+fn json_event() -> CloudEventV1_0 {
+    cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        datacontenttype: "application/json",
+        data: Data::from_serializable(json!({"a": 1, "b": [true]})).unwrap(),
+    )
+    .unwrap()
+}
+
+/// Record of `json_event`.
+const JSON_FIXTURE: &[&[u8]] = &[
+    // attribute: a block of 5 entries
+    b"\x0a",
+    b"\x16specversion\x06\x061.0",
+    b"\x08type\x06\x02t",
+    b"\x0csource\x06\x04/s",
+    b"\x04id\x06\x021",
+    b"\x1edatacontenttype\x06\x20application/json",
+    b"\x00",
+    // data: map branch with a block of 2 entries
+    b"\x06\x04",
+    // a double
+    b"\x02a\x06\x00\x00\x00\x00\x00\x00\xf0\x3f",
+    // a CloudEventData record holding an array of one CloudEventData record holding a boolean
+    b"\x02b\x04\x02\x0avalue\x06\x02\x02\x0avalue\x02\x01\x00\x00\x00",
+    b"\x00",
+];
+
+#[test]
+fn text_event_matches_fixture() {
+    assert_eq!(to_bytes(&text_event()).unwrap(), TEXT_FIXTURE.concat());
+    assert_eq!(from_bytes(&TEXT_FIXTURE.concat()).unwrap(), text_event());
+}
+
+#[test]
+fn json_event_matches_fixture() {
+    assert_eq!(to_bytes(&json_event()).unwrap(), JSON_FIXTURE.concat());
+    assert_eq!(from_bytes(&JSON_FIXTURE.concat()).unwrap(), json_event());
+}
+
+#[test]
+fn data_round_trip() {
+    let data = vec![
+        (None, None),
+        (None, Some(Data::from_binary(b"bytes"))),
+        (Some("text/plain"), Some(Data::from_string("hello"))),
+        (
+            Some("application/json"),
+            Some(Data::from_serializable(true).unwrap()),
+        ),
+        (
+            Some("application/json"),
+            Some(Data::from_serializable(1.5).unwrap()),
+        ),
+        (
+            Some("application/json"),
+            Some(Data::from_serializable("hello").unwrap()),
+        ),
+        (
+            Some("application/json"),
+            Some(Data::from_serializable(json!([{"a": null}, [1, "two"], -3])).unwrap()),
+        ),
+        (
+            Some("application/cloudevents+json"),
+            Some(Data::from_serializable(json!({"a": {"b": {"c": [1.25]}}})).unwrap()),
+        ),
+    ];
+
+    for (datacontenttype, data) in data {
+        let mut builder = CloudEventV1_0Builder::default()
+            .event_type("t")
+            .source("/s")
+            .event_id("1");
+        if let Some(datacontenttype) = datacontenttype {
+            builder = builder.datacontenttype(datacontenttype);
+        }
+        if let Some(data) = data {
+            builder = builder.data(data);
+        }
+        let event = builder.build().unwrap();
+
+        assert_eq!(from_bytes(&to_bytes(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn extensions_are_mapped_to_attributes() {
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "uri".to_owned(),
+        ExtensionValue::from(Url::parse("http://www.google.com/").unwrap()),
+    );
+    extensions.insert(
+        "timestamp".to_owned(),
+        ExtensionValue::Timestamp("2020-01-01T00:00:00Z".parse().unwrap()),
+    );
+    let event = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        time: "2020-01-01T00:00:00Z",
+        extensions: extensions,
+    )
+    .unwrap();
+
+    let record = AvroRecord::try_from(event).unwrap();
+    assert_eq!(
+        record.attribute("time"),
+        Some(&AvroAttribute::String("2020-01-01T00:00:00Z".to_owned()))
+    );
+    assert_eq!(
+        record.attribute("timestamp"),
+        Some(&AvroAttribute::String("2020-01-01T00:00:00Z".to_owned()))
+    );
+    assert_eq!(
+        record.attribute("uri"),
+        Some(&AvroAttribute::String("http://www.google.com/".to_owned()))
+    );
+    assert_eq!(record.data, AvroData::Null);
+}
+
+#[test]
+fn large_integer_extension_is_rejected() {
+    let mut extensions = HashMap::new();
+    extensions.insert("big".to_owned(), ExtensionValue::Integer(i64::MAX));
+    let event = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        extensions: extensions,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        to_bytes(&event),
+        Err(Error::InvalidExtensionValue(ref name)) if name == "big"
+    ));
+}
+
+#[test]
+fn blocks_with_size_are_read() {
+    let record: &[&[u8]] = &[
+        // attribute: a block of -4 entries followed by its size, then a block of 1 entry
+        b"\x07\x5a",
+        b"\x16specversion\x06\x061.0",
+        b"\x08type\x06\x02t",
+        b"\x0csource\x06\x04/s",
+        b"\x0esubject\x00",
+        b"\x02\x04id\x06\x021",
+        b"\x00",
+        // data: null branch
+        b"\x02",
+    ];
+
+    assert_eq!(
+        from_bytes(&record.concat()).unwrap(),
+        cloudevent_v1_0!(
+            event_type: "t",
+            source: "/s",
+            event_id: "1",
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn invalid_records_are_rejected() {
+    let records: &[&[u8]] = &[
+        // truncated
+        b"\x02\x04id",
+        // invalid data branch
+        b"\x00\x0e",
+        // trailing bytes
+        b"\x00\x02\x00",
+        // CloudEventData record without the value key
+        b"\x00\x08\x02\x02\x0aother\x00\x00\x00",
+    ];
+
+    for record in records {
+        assert!(matches!(
+            AvroRecord::from_bytes(record),
+            Err(Error::InvalidAvroRecord(_))
+        ));
+    }
+    assert!(matches!(
+        from_bytes(b"\x00\x02"),
+        Err(Error::MissingRequiredAttribute("specversion"))
+    ));
+}
+
+#[test]
+fn schema_is_shipped() {
+    let schema: serde_json::Value = serde_json::from_str(SCHEMA).unwrap();
+
+    assert_eq!(schema["namespace"], "io.cloudevents");
+    assert_eq!(schema["name"], "CloudEvent");
+    assert_eq!(schema["fields"][0]["name"], "attribute");
+    assert_eq!(schema["fields"][1]["name"], "data");
+}
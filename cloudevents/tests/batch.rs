@@ -0,0 +1,191 @@
+use cloudevents::{cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{Batch, CloudEvent, Data, Error, SpecVersion};
+use serde_json::json;
+
+fn batch() -> Batch {
+    let mut batch = Batch::new();
+    batch.push(
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "1",
+            data: Data::from_string("a string with ], { and \" characters"),
+        )
+        .unwrap(),
+    );
+    batch.push(
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "2",
+            data: Data::from_serializable(json!({"nested": [1, {"a": "]"}]})).unwrap(),
+        )
+        .unwrap(),
+    );
+    batch
+}
+
+#[test]
+fn batch_is_serialized_as_array() {
+    let value = serde_json::to_value(batch()).unwrap();
+
+    let events = value.as_array().unwrap();
+    assert_eq!(events.len(), 2);
+    assert_eq!(events[0]["specversion"], "1.0");
+    assert_eq!(events[1]["specversion"], "0.3");
+}
+
+#[test]
+fn batch_with_mixed_versions_round_trip() {
+    let batch = batch();
+    let json = batch.to_vec().unwrap();
+
+    let parsed = Batch::from_slice(&json).unwrap();
+    assert_eq!(parsed, batch);
+    let versions: Vec<_> = parsed.iter().map(CloudEvent::spec_version).collect();
+    assert_eq!(versions, vec![SpecVersion::V1_0, SpecVersion::V0_3]);
+}
+
+#[test]
+fn batch_can_be_deserialized_with_serde() {
+    let batch = batch();
+    let json = serde_json::to_string(&batch).unwrap();
+
+    assert_eq!(serde_json::from_str::<Batch>(&json).unwrap(), batch);
+}
+
+#[test]
+fn serde_error_reports_index() {
+    let error = serde_json::from_value::<Batch>(json!([
+        {"specversion": "1.0", "type": "test type", "source": "http://www.google.com", "id": "1"},
+        {"specversion": "0.1", "type": "test type", "source": "http://www.google.com", "id": "2"},
+    ]))
+    .unwrap_err();
+
+    assert!(error.to_string().starts_with("invalid event at index 1:"));
+}
+
+#[test]
+fn empty_batch_is_parsed() {
+    assert_eq!(Batch::from_slice(b" [ ] ").unwrap(), Batch::new());
+}
+
+#[test]
+fn invalid_element_is_reported_with_index() {
+    let json = br#"[
+        {"specversion": "1.0", "type": "test type", "source": "http://www.google.com", "id": "1"},
+        {"specversion": "1.0", "type": "test type", "source": "http://www.google.com", "id": "2"},
+        {"specversion": "1.0", "type": "test type", "id": "3"}
+    ]"#;
+
+    let error = Batch::from_slice(json).unwrap_err();
+    assert!(matches!(error, Error::InvalidBatchEvent { index: 2, .. }));
+}
+
+#[test]
+fn reader_continues_after_invalid_element() {
+    let json = br#"[
+        {"specversion": "1.0", "type": "test type", "source": "http://www.google.com"},
+        42,
+        {"specversion": "1.0", "type": "test type", "source": "http://www.google.com", "id": "3"}
+    ]"#;
+
+    let results: Vec<_> = Batch::reader(&json[..]).collect();
+    assert_eq!(results.len(), 3);
+    assert!(matches!(
+        results[0],
+        Err(Error::InvalidBatchEvent { index: 0, .. })
+    ));
+    assert!(matches!(
+        results[1],
+        Err(Error::InvalidBatchEvent { index: 1, .. })
+    ));
+    assert!(results[2].is_ok());
+}
+
+#[test]
+fn reader_stops_at_malformed_array() {
+    let json = br#"[{"specversion": "1.0", "type": "test type""#;
+
+    let results: Vec<_> = Batch::reader(&json[..]).collect();
+    assert_eq!(results.len(), 1);
+    assert!(matches!(results[0], Err(Error::InvalidBatch(_))));
+}
+
+#[test]
+fn trailing_input_is_rejected() {
+    let json = br#"[{"specversion": "1.0", "type": "test type", "source": "/s", "id": "1"}] x"#;
+
+    let results: Vec<_> = Batch::reader(&json[..]).collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(results[1], Err(Error::InvalidBatch(_))));
+
+    assert!(matches!(
+        Batch::from_slice(b"[] []"),
+        Err(Error::InvalidBatch(_))
+    ));
+    assert!(Batch::from_slice(b" [ ] \n").unwrap().is_empty());
+}
+
+#[test]
+fn trailing_comma_is_rejected() {
+    let json = br#"[{"specversion": "1.0", "type": "test type", "source": "/s", "id": "1"}, ]"#;
+
+    let results: Vec<_> = Batch::reader(&json[..]).collect();
+    assert_eq!(results.len(), 2);
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(Error::InvalidBatch("trailing comma in a batch"))
+    ));
+
+    assert!(matches!(
+        Batch::from_slice(b"[,]"),
+        Err(Error::InvalidBatch("missing event in a batch"))
+    ));
+}
+
+#[test]
+fn non_array_is_rejected() {
+    let json = br#"{"specversion": "1.0", "type": "test type"}"#;
+
+    assert!(matches!(
+        Batch::from_slice(json),
+        Err(Error::InvalidBatch(_))
+    ));
+}
+
+#[test]
+fn reader_reads_from_io() {
+    let batch = batch();
+    let json = batch.to_vec().unwrap();
+
+    let parsed = Batch::from_reader(std::io::Cursor::new(json)).unwrap();
+    assert_eq!(parsed, batch);
+}
+
+/// Reader returning one byte per read, so that every element spans several chunks.
+struct ByteByByte<'a>(&'a [u8]);
+
+impl std::io::Read for ByteByByte<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        match self.0.split_first() {
+            Some((b, rest)) if !buf.is_empty() => {
+                buf[0] = *b;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn reader_reads_elements_across_chunks() {
+    let batch = batch();
+    let json = batch.to_vec().unwrap();
+
+    let parsed = Batch::from_reader(ByteByByte(&json)).unwrap();
+    assert_eq!(parsed, batch);
+}
@@ -0,0 +1,88 @@
+#![cfg(feature = "cbor")]
+
+mod common;
+
+use ciborium::value::Value;
+use cloudevents::cbor::{from_bytes, to_bytes};
+use cloudevents::{cloudevent_v0_2, cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{Data, Error};
+use common::binary_event;
+use serde_json::json;
+
+fn entry<'a>(map: &'a [(Value, Value)], name: &str) -> Option<&'a Value> {
+    map.iter()
+        .find(|(key, _)| key.as_text() == Some(name))
+        .map(|(_, value)| value)
+}
+
+#[test]
+fn binary_values_are_byte_strings() {
+    let bytes = to_bytes(&binary_event()).unwrap();
+    let value: Value = ciborium::de::from_reader(&bytes[..]).unwrap();
+    let map = value.as_map().unwrap();
+
+    assert_eq!(
+        entry(map, "data"),
+        Some(&Value::Bytes(b"\x00\xff".to_vec()))
+    );
+    assert_eq!(entry(map, "data_base64"), None);
+    assert_eq!(entry(map, "key"), Some(&Value::Bytes(b"\x01\x02".to_vec())));
+    assert_eq!(
+        entry(map, "specversion"),
+        Some(&Value::Text("1.0".to_owned()))
+    );
+}
+
+#[test]
+fn events_round_trip() {
+    let events = vec![
+        binary_event(),
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            datacontenttype: "application/json",
+            data: Data::from_serializable(json!({"key": [1, "two", null]})).unwrap(),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            data: Data::from_binary(b"\x00\xff"),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            data: Data::from_binary(b"\x00\xff"),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            contenttype: "text/plain",
+            data: Data::from_string("hello"),
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    for event in events {
+        assert_eq!(from_bytes(&to_bytes(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    assert!(matches!(from_bytes(b"\xff"), Err(Error::CborDecode(_))));
+
+    let mut bytes = Vec::new();
+    ciborium::ser::into_writer(&json!({"specversion": "1.0", "id": "id"}), &mut bytes).unwrap();
+    assert!(matches!(from_bytes(&bytes), Err(Error::CborDecode(_))));
+}
@@ -0,0 +1,63 @@
+//! Events shared by the tests of the protocol bindings and event formats.
+#![allow(dead_code)]
+
+use cloudevents::v1_0::CloudEventV1_0Builder;
+use cloudevents::{CloudEvent, CloudEventBuilder, Data, ExtensionValue};
+use serde_json::json;
+use std::collections::HashMap;
+use url::Url;
+
+/// Builder of [`v1_0_event`], to add attributes or extensions to it.
+pub fn v1_0_builder() -> CloudEventV1_0Builder {
+    CloudEventBuilder::v1_0()
+        .event_type("test type")
+        .source("http://www.google.com")
+        .event_id("id")
+        .time("2019-12-04T18:33:09+00:00")
+        .subject("me")
+        .datacontenttype("application/json")
+        .data(Data::from_serializable(json!({"key": "value"})).unwrap())
+        .extension("comment", "hällo wörld")
+}
+
+/// Event with JSON data and a string extension which is not ASCII.
+pub fn v1_0_event() -> CloudEvent {
+    v1_0_builder().build().unwrap().into()
+}
+
+/// Event with binary data and a binary extension.
+pub fn binary_event() -> CloudEvent {
+    CloudEventBuilder::v1_0()
+        .event_type("test type")
+        .source("http://www.google.com")
+        .event_id("id")
+        .time("2020-01-01T00:00:00Z")
+        .datacontenttype("application/octet-stream")
+        .data(Data::from_binary(b"\x00\xff"))
+        .extension("key", &b"\x01\x02"[..])
+        .build()
+        .unwrap()
+        .into()
+}
+
+/// One extension of each type of the CloudEvents type system.
+pub fn typed_extensions() -> HashMap<String, ExtensionValue> {
+    let mut extensions = HashMap::new();
+    extensions.insert("boolean".to_owned(), ExtensionValue::from(true));
+    extensions.insert("integer".to_owned(), ExtensionValue::from(42));
+    extensions.insert("string".to_owned(), ExtensionValue::from("a < b"));
+    extensions.insert("binary".to_owned(), ExtensionValue::from(&b"bytes"[..]));
+    extensions.insert(
+        "uri".to_owned(),
+        ExtensionValue::from(Url::parse("http://www.google.com/").unwrap()),
+    );
+    extensions.insert(
+        "uriref".to_owned(),
+        ExtensionValue::UriRef("/path".to_owned()),
+    );
+    extensions.insert(
+        "timestamp".to_owned(),
+        ExtensionValue::Timestamp("2019-12-04T18:33:09.123456789Z".parse().unwrap()),
+    );
+    extensions
+}
@@ -0,0 +1,257 @@
+use cloudevents::v0_2::CloudEventV0_2;
+use cloudevents::v0_3::CloudEventV0_3;
+use cloudevents::v1_0::CloudEventV1_0;
+use cloudevents::{cloudevent_v0_2, cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+fn extensions() -> HashMap<String, ExtensionValue> {
+    let mut extensions = HashMap::new();
+    extensions.insert("ext".to_owned(), ExtensionValue::from_string("value"));
+    extensions
+}
+
+#[test]
+fn v0_2_can_be_upgraded_to_v1_0() {
+    let event = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        time: "2019-12-04T18:33:09+00:00",
+        schemaurl: "http://www.google.com/schema.json",
+        contenttype: "application/json",
+        data: Data::from_string("\"test\""),
+        extensions: extensions(),
+    )
+    .unwrap();
+
+    let event = CloudEventV1_0::try_from(event).unwrap();
+
+    assert_eq!(event.event_type(), "test type");
+    assert_eq!(event.source(), "http://www.google.com");
+    assert_eq!(event.event_id(), "id");
+    assert_eq!(
+        event.event_time().map(|t| t.to_rfc3339()),
+        Some("2019-12-04T18:33:09+00:00".to_owned())
+    );
+    assert_eq!(event.subject(), None);
+    assert_eq!(
+        event.dataschema(),
+        Some("http://www.google.com/schema.json")
+    );
+    assert_eq!(event.datacontenttype(), Some("application/json"));
+    assert_eq!(event.data(), Some(&Data::from_string("\"test\"")));
+    assert_eq!(event.extensions(), Some(&extensions()));
+}
+
+#[test]
+fn v1_0_can_be_downgraded_to_v0_2() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        dataschema: "http://www.google.com/schema.json",
+        datacontenttype: "application/json",
+    )
+    .unwrap();
+
+    let event = CloudEventV0_2::try_from(event).unwrap();
+
+    assert_eq!(
+        event.schema_url(),
+        Some("http://www.google.com/schema.json")
+    );
+    assert_eq!(event.contenttype(), Some("application/json"));
+}
+
+#[test]
+fn v1_0_with_subject_cannot_be_downgraded_to_v0_2() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        subject: "me",
+    )
+    .unwrap();
+
+    let error = CloudEventV0_2::try_from(event).unwrap_err();
+    assert!(error.to_string().contains("subject"));
+}
+
+#[test]
+fn v1_0_roundtrips_through_v0_3() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        subject: "me",
+        dataschema: "http://www.google.com/schema.json",
+        datacontenttype: "application/json",
+        extensions: extensions(),
+    )
+    .unwrap();
+
+    let converted = CloudEventV0_3::from(event.clone());
+    assert_eq!(
+        converted.schema_url(),
+        Some("http://www.google.com/schema.json")
+    );
+    assert_eq!(CloudEventV1_0::try_from(converted).unwrap(), event);
+}
+
+#[test]
+fn v0_3_with_datacontentencoding_reports_all_unrepresentable_attributes() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        subject: "me",
+        datacontentencoding: "quoted-printable",
+    )
+    .unwrap();
+
+    let error = CloudEventV0_2::try_from(event).unwrap_err().to_string();
+    assert!(error.contains("subject"));
+    assert!(error.contains("datacontentencoding"));
+}
+
+#[test]
+fn to_version_converts_wrapped_event() {
+    let event = CloudEvent::from(
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+        )
+        .unwrap(),
+    );
+
+    let upgraded = event.to_version(SpecVersion::V1_0).unwrap();
+    assert_eq!(upgraded.spec_version(), SpecVersion::V1_0);
+    let downgraded = upgraded.to_version(SpecVersion::V0_2).unwrap();
+    assert_eq!(downgraded.spec_version(), SpecVersion::V0_2);
+    match (event, downgraded) {
+        (CloudEvent::V0_2(original), CloudEvent::V0_2(converted)) => {
+            assert_eq!(original, converted)
+        }
+        _ => panic!("expected v0.2 events"),
+    }
+}
+
+#[test]
+fn to_version_serializes_target_specversion() {
+    let event = CloudEvent::from(
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+        )
+        .unwrap(),
+    );
+
+    let json = serde_json::to_value(event.to_version(SpecVersion::V1_0).unwrap()).unwrap();
+    assert_eq!(json["specversion"], "1.0");
+}
+
+#[test]
+fn v0_3_base64_data_is_upgraded_to_binary_data() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontentencoding: "base64",
+        data: Data::from_string("dGVzdA=="),
+    )
+    .unwrap();
+
+    let event = CloudEventV1_0::try_from(event).unwrap();
+    assert_eq!(event.data(), Some(&Data::Binary(b"test".to_vec())));
+
+    let event = CloudEventV0_3::from(event);
+    assert_eq!(event.datacontentencoding(), Some("base64"));
+}
+
+#[test]
+fn v0_3_base64_datacontentencoding_is_case_insensitive() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontentencoding: "Base64",
+        data: Data::from_string("dGVzdA=="),
+    )
+    .unwrap();
+
+    let event = CloudEventV1_0::try_from(event).unwrap();
+    assert_eq!(event.data(), Some(&Data::Binary(b"test".to_vec())));
+}
+
+#[test]
+fn extensions_outside_of_the_v1_0_type_system_cannot_be_upgraded() {
+    let mut extensions = extensions();
+    extensions.insert(
+        "nested".to_owned(),
+        ExtensionValue::from_serializable(json!({"key": "value"})).unwrap(),
+    );
+    extensions.insert("large".to_owned(), ExtensionValue::from(i64::MAX));
+    let v0_2 = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        extensions: extensions.clone(),
+    )
+    .unwrap();
+    let v0_3 = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        extensions: extensions,
+    )
+    .unwrap();
+
+    for error in &[
+        CloudEventV1_0::try_from(v0_2).unwrap_err(),
+        CloudEventV1_0::try_from(v0_3).unwrap_err(),
+    ] {
+        assert!(matches!(
+            error,
+            Error::UnrepresentableAttributes {
+                version: SpecVersion::V1_0,
+                ref attributes,
+            } if attributes == &["large", "nested"]
+        ));
+    }
+}
+
+#[test]
+fn binary_data_is_downgraded_to_v0_2_directly_and_through_v0_3() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        data: Data::from_binary(b"test"),
+    )
+    .unwrap();
+
+    let direct = CloudEventV0_2::try_from(event.clone()).unwrap();
+    let through_v0_3 = CloudEventV0_2::try_from(CloudEventV0_3::from(event)).unwrap();
+    assert_eq!(direct.data(), Some(&Data::Binary(b"test".to_vec())));
+    assert_eq!(through_v0_3, direct);
+}
+
+#[test]
+fn v0_3_base64_data_is_downgraded_to_binary_data() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontentencoding: "base64",
+        data: Data::from_string("dGVzdA=="),
+    )
+    .unwrap();
+
+    let event = CloudEventV0_2::try_from(event).unwrap();
+    assert_eq!(event.data(), Some(&Data::Binary(b"test".to_vec())));
+}
@@ -0,0 +1,135 @@
+use cloudevents::v0_2::CloudEventV0_2Builder;
+use cloudevents::v0_3::CloudEventV0_3Builder;
+use cloudevents::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use cloudevents::{CloudEvent, Data, Error};
+use serde_derive::Deserialize;
+use serde_json::json;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Order {
+    id: u32,
+    items: Vec<String>,
+}
+
+fn order() -> Order {
+    Order {
+        id: 1,
+        items: vec!["book".to_owned()],
+    }
+}
+
+fn event(datacontenttype: Option<&str>, data: Data) -> CloudEventV1_0 {
+    let mut builder = CloudEventV1_0Builder::default()
+        .event_type("t")
+        .source("/s")
+        .event_id("1")
+        .data(data);
+    if let Some(datacontenttype) = datacontenttype {
+        builder = builder.datacontenttype(datacontenttype);
+    }
+    builder.build().unwrap()
+}
+
+#[test]
+fn json_data_is_decoded() {
+    let json = r#"{"id": 1, "items": ["book"]}"#;
+    let data = vec![
+        Data::from_serializable(json!({"id": 1, "items": ["book"]})).unwrap(),
+        Data::from_string(json),
+        Data::from_binary(json),
+    ];
+
+    for content_type in &[
+        None,
+        Some("application/json"),
+        Some("application/json; charset=utf-8"),
+        Some("application/vnd.order+json"),
+    ] {
+        for data in &data {
+            let event = event(*content_type, data.clone());
+            assert_eq!(event.data_as::<Order>().unwrap(), Some(order()));
+        }
+    }
+}
+
+#[test]
+fn text_data_is_decoded() {
+    for data in &[Data::from_string("hello"), Data::from_binary(b"hello")] {
+        let event = event(Some("text/plain"), data.clone());
+        assert_eq!(event.data_as::<String>().unwrap(), Some("hello".to_owned()));
+    }
+
+    let event = event(Some("text/plain"), Data::from_binary(b"\xff"));
+    assert!(matches!(event.data_as::<String>(), Err(Error::Json(_))));
+}
+
+#[test]
+fn binary_data_is_decoded() {
+    let event = event(Some("image/png"), Data::from_binary(b"\x89PNG"));
+    assert_eq!(
+        event.data_as::<Vec<u8>>().unwrap(),
+        Some(b"\x89PNG".to_vec())
+    );
+
+    let v0_3 = CloudEventV0_3Builder::default()
+        .event_type("t")
+        .source("/s")
+        .event_id("1")
+        .datacontenttype("application/octet-stream")
+        .datacontentencoding("base64")
+        .data(Data::from_string("iVBORw=="))
+        .build()
+        .unwrap();
+    assert_eq!(
+        v0_3.data_as::<Vec<u8>>().unwrap(),
+        Some(b"\x89PNG".to_vec())
+    );
+}
+
+#[test]
+fn unsupported_content_type_is_rejected() {
+    let event = event(Some("image/png"), Data::from_string("not binary"));
+    assert!(matches!(
+        event.data_as::<Vec<u8>>(),
+        Err(Error::UnsupportedContentType(ref t)) if t == "image/png"
+    ));
+}
+
+#[test]
+fn mismatched_data_is_rejected() {
+    let event = event(Some("application/json"), Data::from_string("{"));
+    assert!(matches!(event.data_as::<Order>(), Err(Error::Json(_))));
+}
+
+#[test]
+fn missing_data_is_none() {
+    let event = CloudEventV1_0Builder::default()
+        .event_type("t")
+        .source("/s")
+        .event_id("1")
+        .build()
+        .unwrap();
+    assert_eq!(event.data_as::<Order>().unwrap(), None);
+}
+
+#[test]
+fn cloud_event_reads_content_type_of_each_version() {
+    let v0_2 = CloudEventV0_2Builder::default()
+        .event_type("t")
+        .source("/s")
+        .event_id("1")
+        .contenttype("text/plain")
+        .data(Data::from_string("[1]"))
+        .build()
+        .unwrap();
+    assert_eq!(
+        CloudEvent::V0_2(v0_2).data_as::<String>().unwrap(),
+        Some("[1]".to_owned())
+    );
+
+    let v1_0 = event(None, Data::from_string("[1]"));
+    assert_eq!(
+        CloudEvent::V1_0(v1_0).data_as::<Vec<u8>>().unwrap(),
+        Some(vec![1])
+    );
+}
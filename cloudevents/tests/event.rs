@@ -0,0 +1,120 @@
+use cloudevents::v1_0::CloudEventV1_0;
+use cloudevents::{CloudEvent, Error, SpecVersion};
+use serde_json::json;
+
+#[test]
+fn deserialize_dispatches_on_specversion() {
+    for (specversion, expected) in &[
+        ("0.2", SpecVersion::V0_2),
+        ("0.3", SpecVersion::V0_3),
+        ("1.0", SpecVersion::V1_0),
+    ] {
+        let event: CloudEvent = serde_json::from_value(json!({
+            "type": "test type",
+            "specversion": specversion,
+            "source": "http://www.google.com",
+            "id": "id",
+        }))
+        .unwrap();
+
+        assert_eq!(event.spec_version(), *expected);
+    }
+}
+
+#[test]
+fn deserialize_v0_2_keeps_contenttype() {
+    let event: CloudEvent = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "0.2",
+        "source": "http://www.google.com",
+        "id": "id",
+        "contenttype": "application/json",
+    }))
+    .unwrap();
+
+    match event {
+        CloudEvent::V0_2(e) => assert_eq!(e.contenttype(), Some("application/json")),
+        _ => panic!("expected a v0.2 event"),
+    }
+}
+
+#[test]
+fn deserialize_rejects_unknown_specversion() {
+    let error = serde_json::from_value::<CloudEvent>(json!({
+        "type": "test type",
+        "specversion": "0.1",
+        "source": "http://www.google.com",
+        "id": "id",
+    }))
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "Unknown spec version: 0.1");
+
+    let error =
+        r#"{"type":"test type","specversion":"0.1","source":"http://www.google.com","id":"id"}"#
+            .parse::<CloudEvent>()
+            .unwrap_err();
+    assert!(matches!(error, Error::UnknownSpecVersion(ref v) if v == "0.1"));
+}
+
+#[test]
+fn deserialize_rejects_duplicate_attributes() {
+    for json in &[
+        r#"{"type":"t","specversion":"1.0","source":"/s","id":"1","id":"2"}"#,
+        r#"{"type":"t","specversion":"1.0","source":"/s","id":"1","ext":1,"ext":2}"#,
+    ] {
+        let error = serde_json::from_str::<CloudEvent>(json).unwrap_err();
+        assert!(error.to_string().starts_with("duplicate field"));
+    }
+
+    let error = serde_json::from_str::<CloudEventV1_0>(
+        r#"{"type":"t","specversion":"1.0","source":"/s","id":"1","data":null,"data":1}"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with("duplicate field `data`"));
+}
+
+#[test]
+fn deserialize_rejects_missing_specversion() {
+    let error = serde_json::from_value::<CloudEvent>(json!({
+        "type": "test type",
+        "source": "http://www.google.com",
+        "id": "id",
+    }))
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "missing field `specversion`");
+}
+
+#[test]
+fn deserialize_reports_invalid_attribute() {
+    let error = serde_json::from_value::<CloudEvent>(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+        "id": "id",
+        "time": "yesterday",
+    }))
+    .unwrap_err();
+
+    assert!(error.to_string().starts_with("invalid attribute `time`"));
+}
+
+#[test]
+fn deserialize_reports_missing_attribute() {
+    let error = serde_json::from_value::<CloudEvent>(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+    }))
+    .unwrap_err();
+
+    assert_eq!(error.to_string(), "missing field `id`");
+}
+
+#[test]
+fn unknown_spec_version_cannot_be_parsed() {
+    let error = "0.1".parse::<SpecVersion>().unwrap_err();
+
+    assert!(matches!(error, Error::UnknownSpecVersion(ref v) if v == "0.1"));
+}
@@ -0,0 +1,157 @@
+use cloudevents::v1_0::{CloudEventRef, CloudEventV1_0, DataRef};
+use cloudevents::{Data, Error};
+use std::convert::TryFrom;
+
+const EVENT: &str = r#"{
+    "specversion": "1.0",
+    "type": "test type",
+    "source": "http://www.google.com",
+    "id": "id",
+    "time": "2020-01-01T00:00:00Z",
+    "subject": "escaped \"subject\"",
+    "dataschema": "http://www.google.com/schema.json",
+    "datacontenttype": "application/json",
+    "count": 42,
+    "name": "value",
+    "data": {"a": [1, "two"]}
+}"#;
+
+#[test]
+fn getters_return_the_attributes() {
+    let event: CloudEventRef = serde_json::from_str(EVENT).unwrap();
+
+    assert_eq!(event.event_type(), "test type");
+    assert_eq!(event.source(), "http://www.google.com");
+    assert_eq!(event.event_id(), "id");
+    assert_eq!(
+        event.event_time(),
+        Some(&"2020-01-01T00:00:00Z".parse().unwrap())
+    );
+    assert_eq!(event.subject(), Some("escaped \"subject\""));
+    assert_eq!(
+        event.dataschema(),
+        Some("http://www.google.com/schema.json")
+    );
+    assert_eq!(event.datacontenttype(), Some("application/json"));
+    assert!(
+        matches!(event.data(), Some(DataRef::Json(raw)) if raw.get() == r#"{"a": [1, "two"]}"#)
+    );
+
+    let extensions: Vec<_> = event
+        .extensions()
+        .map(|(name, value)| (name, value.get()))
+        .collect();
+    assert_eq!(extensions, vec![("count", "42"), ("name", r#""value""#)]);
+}
+
+#[test]
+fn conversion_matches_owned_deserialization() {
+    let binary = r#"{
+        "specversion": "1.0",
+        "type": "test type",
+        "source": "http://www.google.com",
+        "id": "id",
+        "data_base64": "Ynl0ZXM="
+    }"#;
+
+    for json in &[EVENT, binary] {
+        let event: CloudEventRef = serde_json::from_slice(json.as_bytes()).unwrap();
+        assert_eq!(
+            CloudEventV1_0::try_from(event).unwrap(),
+            serde_json::from_str::<CloudEventV1_0>(json).unwrap()
+        );
+    }
+
+    let event: CloudEventRef = serde_json::from_str(binary).unwrap();
+    assert!(matches!(event.data(), Some(DataRef::Base64(encoded)) if encoded == "Ynl0ZXM="));
+    assert_eq!(
+        CloudEventV1_0::try_from(event).unwrap().data(),
+        Some(&Data::from_binary(b"bytes"))
+    );
+}
+
+#[test]
+fn invalid_events_are_rejected() {
+    let invalid = &[
+        r#"{"specversion": "0.3", "type": "t", "source": "/s", "id": "1"}"#,
+        r#"{"specversion": "1.0", "source": "/s", "id": "1"}"#,
+        r#"{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", "data": 1, "data_base64": ""}"#,
+        r#"{"specversion": "1.0", "type": 1, "source": "/s", "id": "1"}"#,
+    ];
+    for json in invalid {
+        assert!(serde_json::from_str::<CloudEventRef>(json).is_err());
+    }
+
+    let event: CloudEventRef = serde_json::from_str(
+        r#"{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", "data_base64": "%"}"#,
+    )
+    .unwrap();
+    assert!(matches!(
+        CloudEventV1_0::try_from(event),
+        Err(Error::InvalidBase64 {
+            attribute: "data_base64",
+            ..
+        })
+    ));
+}
+
+#[test]
+fn duplicate_attributes_are_rejected() {
+    for json in &[
+        r#"{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", "id": "2"}"#,
+        r#"{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", "ext": 1, "ext": 2}"#,
+        r#"{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", "data": 1, "data": 2}"#,
+    ] {
+        let error = serde_json::from_str::<CloudEventRef>(json).unwrap_err();
+        assert!(error.to_string().starts_with("duplicate field"));
+    }
+}
+
+#[test]
+fn data_as_matches_owned_decoding() {
+    let events = &[
+        (None, r#""data": {"a": [1, "two"]}"#),
+        (
+            Some("application/json"),
+            r#""data": "{\"a\": [1, \"two\"]}""#,
+        ),
+        (Some("text/plain"), r#""data": "escaped \"text\"""#),
+        (Some("text/plain"), r#""data": 42"#),
+        (
+            Some("application/octet-stream"),
+            r#""data_base64": "Ynl0ZXM=""#,
+        ),
+        (Some("image/png"), r#""data": "not binary""#),
+    ];
+
+    for (content_type, data) in events {
+        let content_type = content_type
+            .map(|t| format!(r#""datacontenttype": "{}", "#, t))
+            .unwrap_or_default();
+        let json = format!(
+            r#"{{"specversion": "1.0", "type": "t", "source": "/s", "id": "1", {}{}}}"#,
+            content_type, data
+        );
+        let event: CloudEventRef = serde_json::from_str(&json).unwrap();
+        let owned = CloudEventV1_0::try_from(event.clone()).unwrap();
+
+        assert_eq!(
+            event.data_as::<serde_json::Value>().ok(),
+            owned.data_as::<serde_json::Value>().ok()
+        );
+        assert_eq!(
+            event.data_as::<String>().ok(),
+            owned.data_as::<String>().ok()
+        );
+        assert_eq!(
+            event.data_as::<Vec<u8>>().ok(),
+            owned.data_as::<Vec<u8>>().ok()
+        );
+    }
+
+    let event: CloudEventRef = serde_json::from_str(EVENT).unwrap();
+    assert_eq!(
+        event.data_as::<serde_json::Value>().unwrap(),
+        Some(serde_json::json!({"a": [1, "two"]}))
+    );
+}
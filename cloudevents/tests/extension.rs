@@ -0,0 +1,100 @@
+use chrono::prelude::{DateTime, Utc};
+use cloudevents::ExtensionValue;
+use serde_json::json;
+use url::Url;
+
+#[test]
+fn values_can_be_created_from_primitives() {
+    assert_eq!(ExtensionValue::from(true), ExtensionValue::Boolean(true));
+    assert_eq!(ExtensionValue::from(42), ExtensionValue::Integer(42));
+    assert_eq!(
+        ExtensionValue::from("value"),
+        ExtensionValue::String("value".to_owned())
+    );
+    assert_eq!(
+        ExtensionValue::from(b"value".to_vec()),
+        ExtensionValue::Binary(b"value".to_vec())
+    );
+}
+
+#[test]
+fn values_can_be_created_from_url_and_timestamp() {
+    let url = Url::parse("https://github.com/cloudevents/spec").unwrap();
+    assert_eq!(ExtensionValue::from(url.clone()), ExtensionValue::Uri(url));
+
+    let time = DateTime::parse_from_rfc3339("2019-12-04T18:33:09+00:00")
+        .unwrap()
+        .with_timezone(&Utc);
+    assert_eq!(
+        ExtensionValue::from(time),
+        ExtensionValue::Timestamp(time.into())
+    );
+}
+
+#[test]
+fn values_have_canonical_string_encoding() {
+    assert_eq!(ExtensionValue::Boolean(false).to_string(), "false");
+    assert_eq!(ExtensionValue::Integer(-12).to_string(), "-12");
+    assert_eq!(ExtensionValue::from_string("value").to_string(), "value");
+    assert_eq!(
+        ExtensionValue::Binary(b"this is binary".to_vec()).to_string(),
+        "dGhpcyBpcyBiaW5hcnk="
+    );
+    assert_eq!(
+        ExtensionValue::from_uri_ref("/cloudevents/spec").to_string(),
+        "/cloudevents/spec"
+    );
+    assert_eq!(
+        ExtensionValue::Timestamp(
+            DateTime::parse_from_rfc3339("2019-12-04T18:33:09+01:00").unwrap()
+        )
+        .to_string(),
+        "2019-12-04T18:33:09+01:00"
+    );
+    assert_eq!(
+        ExtensionValue::Timestamp(
+            DateTime::parse_from_rfc3339("2019-12-04T18:33:09.250+00:00").unwrap()
+        )
+        .to_string(),
+        "2019-12-04T18:33:09.250Z"
+    );
+}
+
+#[test]
+fn json_values_are_deserialized_into_typed_values() {
+    let values: Vec<ExtensionValue> =
+        serde_json::from_value(json!([true, 42, "value", 1.5, {"a": "b"}])).unwrap();
+
+    assert_eq!(
+        values,
+        vec![
+            ExtensionValue::Boolean(true),
+            ExtensionValue::Integer(42),
+            ExtensionValue::String("value".to_owned()),
+            ExtensionValue::Object(json!(1.5)),
+            ExtensionValue::Object(json!({"a": "b"})),
+        ]
+    );
+}
+
+#[test]
+fn typed_values_are_serialized_with_their_json_type() {
+    let url = Url::parse("https://github.com/cloudevents/spec").unwrap();
+    let json = serde_json::to_value(vec![
+        ExtensionValue::Boolean(true),
+        ExtensionValue::Integer(42),
+        ExtensionValue::Uri(url),
+        ExtensionValue::Binary(b"this is binary".to_vec()),
+    ])
+    .unwrap();
+
+    assert_eq!(
+        json,
+        json!([
+            true,
+            42,
+            "https://github.com/cloudevents/spec",
+            "dGhpcyBpcyBiaW5hcnk="
+        ])
+    );
+}
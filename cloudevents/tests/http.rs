@@ -0,0 +1,229 @@
+#![cfg(feature = "http")]
+
+mod common;
+
+use cloudevents::http::{
+    batch_from_request, batch_from_response, content_mode, from_request, from_response,
+    to_batch_request, to_batch_response, to_binary_request, to_binary_response,
+    to_structured_request, to_structured_response, ContentMode,
+};
+use cloudevents::{cloudevent_v0_2, cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{CloudEvent, Data, Error};
+use common::v1_0_event;
+use http::{Request, Response, StatusCode};
+
+#[test]
+fn binary_request_has_ce_headers() {
+    let request = to_binary_request(Request::post("http://localhost"), &v1_0_event()).unwrap();
+    let headers = request.headers();
+
+    assert_eq!(request.method(), "POST");
+    assert_eq!(headers["ce-specversion"], "1.0");
+    assert_eq!(headers["ce-type"], "test%20type");
+    assert_eq!(headers["ce-source"], "http://www.google.com");
+    assert_eq!(headers["ce-id"], "id");
+    assert_eq!(headers["ce-time"], "2019-12-04T18:33:09Z");
+    assert_eq!(headers["ce-subject"], "me");
+    assert_eq!(headers["ce-comment"], "h%C3%A4llo%20w%C3%B6rld");
+    assert_eq!(headers["content-type"], "application/json");
+    assert!(!headers.contains_key("ce-datacontenttype"));
+    assert_eq!(request.body(), br#"{"key":"value"}"#);
+}
+
+#[test]
+fn binary_request_round_trip() {
+    let event = v1_0_event();
+    let request = to_binary_request(Request::post("http://localhost"), &event).unwrap();
+
+    assert_eq!(from_request(&request).unwrap(), event);
+}
+
+#[test]
+fn binary_response_round_trip() {
+    let event: CloudEvent = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        contenttype: "text/plain",
+        data: Data::from_string("hello"),
+    )
+    .unwrap()
+    .into();
+    let response = to_binary_response(Response::builder().status(StatusCode::OK), &event).unwrap();
+
+    assert_eq!(response.headers()["content-type"], "text/plain");
+    assert!(!response.headers().contains_key("ce-contenttype"));
+    assert_eq!(from_response(&response).unwrap(), event);
+}
+
+#[test]
+fn binary_data_is_carried_as_raw_body() {
+    let event: CloudEvent = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontenttype: "application/octet-stream",
+        data: Data::from_binary([0, 159, 146, 150]),
+    )
+    .unwrap()
+    .into();
+    let request = to_binary_request(Request::post("http://localhost"), &event).unwrap();
+
+    assert_eq!(request.body(), &[0, 159, 146, 150]);
+    assert_eq!(from_request(&request).unwrap(), event);
+}
+
+#[test]
+fn v0_3_binary_data_is_decoded_from_raw_body() {
+    let event: CloudEvent = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        data: Data::from_binary(b"bytes"),
+    )
+    .unwrap()
+    .into();
+    let request = to_binary_request(Request::post("http://localhost"), &event).unwrap();
+
+    assert!(!request.headers().contains_key("ce-datacontentencoding"));
+    assert_eq!(request.body(), b"bytes");
+    match from_request(&request).unwrap() {
+        CloudEvent::V0_3(e) => assert_eq!(e.data(), Some(&Data::from_binary(b"bytes"))),
+        _ => panic!("expected a v0.3 event"),
+    }
+}
+
+#[test]
+fn headers_set_on_the_builder_are_kept() {
+    let request = to_binary_request(
+        Request::post("http://localhost").header("authorization", "token"),
+        &v1_0_event(),
+    )
+    .unwrap();
+
+    assert_eq!(request.headers()["authorization"], "token");
+}
+
+#[test]
+fn missing_specversion_header_is_reported() {
+    let request = Request::post("http://localhost")
+        .header("ce-id", "id")
+        .body(Vec::new())
+        .unwrap();
+
+    let error = from_request(&request).unwrap_err();
+    assert!(matches!(
+        error,
+        Error::MissingRequiredAttribute("specversion")
+    ));
+}
+
+#[test]
+fn invalid_header_value_is_reported() {
+    let request = Request::post("http://localhost")
+        .header("ce-specversion", "1.0")
+        .header("ce-type", "test type")
+        .header("ce-source", "http://www.google.com")
+        .header("ce-id", "%FF")
+        .body(Vec::new())
+        .unwrap();
+
+    let error = from_request(&request).unwrap_err();
+    assert!(matches!(error, Error::InvalidHeaderValue(ref name) if name == "ce-id"));
+}
+
+#[test]
+fn structured_request_carries_json_event() {
+    let event = v1_0_event();
+    let request = to_structured_request(Request::post("http://localhost"), &event).unwrap();
+
+    assert_eq!(content_mode(request.headers()), ContentMode::Structured);
+    assert!(!request.headers().contains_key("ce-id"));
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(request.body()).unwrap(),
+        serde_json::to_value(&event).unwrap()
+    );
+    assert_eq!(from_request(&request).unwrap(), event);
+}
+
+#[test]
+fn structured_response_round_trip() {
+    let event = v1_0_event();
+    let response = to_structured_response(Response::builder(), &event).unwrap();
+
+    assert_eq!(from_response(&response).unwrap(), event);
+}
+
+#[test]
+fn structured_request_with_charset_is_decoded() {
+    let request = Request::post("http://localhost")
+        .header("content-type", "application/cloudevents+json; charset=utf-8")
+        .body(
+            r#"{"specversion":"0.2","type":"test type","source":"http://www.google.com","id":"id"}"#,
+        )
+        .unwrap();
+
+    match from_request(&request).unwrap() {
+        CloudEvent::V0_2(e) => assert_eq!(e.event_id(), "id"),
+        _ => panic!("expected a v0.2 event"),
+    }
+}
+
+#[test]
+fn unsupported_event_format_is_reported() {
+    let request = Request::post("http://localhost")
+        .header("content-type", "application/cloudevents+xml")
+        .body(Vec::new())
+        .unwrap();
+
+    let error = from_request(&request).unwrap_err();
+    assert!(
+        matches!(error, Error::UnsupportedContentType(ref t) if t == "application/cloudevents+xml")
+    );
+}
+
+#[test]
+fn batch_request_round_trip() {
+    let events = vec![
+        v1_0_event(),
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+        )
+        .unwrap()
+        .into(),
+    ];
+    let request = to_batch_request(Request::post("http://localhost"), &events).unwrap();
+
+    assert_eq!(
+        request.headers()["content-type"],
+        "application/cloudevents-batch+json; charset=UTF-8"
+    );
+    assert_eq!(content_mode(request.headers()), ContentMode::Batch);
+    assert_eq!(batch_from_request(&request).unwrap(), events);
+}
+
+#[test]
+fn batch_response_round_trip() {
+    let events = vec![v1_0_event(), v1_0_event()];
+    let response = to_batch_response(Response::builder(), &events).unwrap();
+
+    assert_eq!(batch_from_response(&response).unwrap(), events);
+}
+
+#[test]
+fn single_event_is_read_as_batch_of_one() {
+    let event = v1_0_event();
+    let request = to_binary_request(Request::post("http://localhost"), &event).unwrap();
+
+    assert_eq!(batch_from_request(&request).unwrap(), vec![event]);
+}
+
+#[test]
+fn batch_is_not_read_as_single_event() {
+    let request = to_batch_request(Request::post("http://localhost"), &[v1_0_event()]).unwrap();
+
+    let error = from_request(&request).unwrap_err();
+    assert!(matches!(error, Error::UnsupportedContentType(_)));
+}
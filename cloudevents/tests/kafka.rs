@@ -0,0 +1,162 @@
+mod common;
+
+use cloudevents::cloudevent_v0_2;
+use cloudevents::kafka::{from_record, to_binary_record, to_structured_record, KafkaRecord};
+use cloudevents::{CloudEvent, Data, Error};
+use common::{v1_0_builder, v1_0_event};
+
+fn header(name: &str, value: &str) -> (String, Vec<u8>) {
+    (name.to_owned(), value.as_bytes().to_vec())
+}
+
+#[test]
+fn binary_record_has_ce_headers() {
+    let event: CloudEvent = v1_0_builder()
+        .extension("partitionkey", "user-42")
+        .build()
+        .unwrap()
+        .into();
+    let record = to_binary_record(&event).unwrap();
+
+    assert_eq!(
+        record.headers,
+        vec![
+            header("ce_specversion", "1.0"),
+            header("ce_type", "test type"),
+            header("ce_source", "http://www.google.com"),
+            header("ce_id", "id"),
+            header("ce_time", "2019-12-04T18:33:09Z"),
+            header("ce_subject", "me"),
+            header("content-type", "application/json"),
+            header("ce_comment", "hällo wörld"),
+            header("ce_partitionkey", "user-42"),
+        ]
+    );
+    assert_eq!(record.key.as_deref(), Some(&b"user-42"[..]));
+    assert_eq!(record.value.as_deref(), Some(&br#"{"key":"value"}"#[..]));
+}
+
+#[test]
+fn binary_record_round_trip() {
+    let event = v1_0_event();
+    let record = to_binary_record(&event).unwrap();
+
+    assert_eq!(from_record(&record).unwrap(), event);
+}
+
+#[test]
+fn binary_record_without_data_has_no_value() {
+    let event: CloudEvent = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+    )
+    .unwrap()
+    .into();
+
+    let record = to_binary_record(&event).unwrap();
+    assert_eq!(record.key, None);
+    assert_eq!(record.value, None);
+    assert_eq!(from_record(&record).unwrap(), event);
+}
+
+#[test]
+fn binary_record_maps_v0_2_content_type() {
+    let event: CloudEvent = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        contenttype: "text/plain",
+        data: Data::from_string("hello"),
+    )
+    .unwrap()
+    .into();
+
+    let record = to_binary_record(&event).unwrap();
+    assert_eq!(record.header("content-type"), Some(&b"text/plain"[..]));
+    assert_eq!(record.header("ce_contenttype"), None);
+    assert_eq!(from_record(&record).unwrap(), event);
+}
+
+#[test]
+fn structured_record_round_trip() {
+    let event: CloudEvent = v1_0_builder()
+        .extension("partitionkey", "user-42")
+        .build()
+        .unwrap()
+        .into();
+    let record = to_structured_record(&event).unwrap();
+
+    assert_eq!(
+        record.headers,
+        vec![header("content-type", "application/cloudevents+json")]
+    );
+    assert_eq!(record.key.as_deref(), Some(&b"user-42"[..]));
+    assert_eq!(from_record(&record).unwrap(), event);
+}
+
+#[test]
+fn record_without_spec_version_is_rejected() {
+    let record = KafkaRecord {
+        headers: vec![header("ce_id", "id")],
+        ..KafkaRecord::default()
+    };
+
+    assert!(matches!(
+        from_record(&record),
+        Err(Error::MissingRequiredAttribute("specversion"))
+    ));
+}
+
+#[test]
+fn header_with_invalid_utf8_is_rejected() {
+    let record = KafkaRecord {
+        headers: vec![
+            header("ce_specversion", "1.0"),
+            ("ce_id".to_owned(), vec![0xff, 0xfe]),
+        ],
+        ..KafkaRecord::default()
+    };
+
+    assert!(matches!(
+        from_record(&record),
+        Err(Error::InvalidHeaderValue(ref name)) if name == "ce_id"
+    ));
+}
+
+#[test]
+fn batch_record_is_rejected() {
+    let record = KafkaRecord {
+        headers: vec![header("content-type", "application/cloudevents-batch+json")],
+        value: Some(b"[]".to_vec()),
+        ..KafkaRecord::default()
+    };
+
+    assert!(matches!(
+        from_record(&record),
+        Err(Error::UnsupportedContentType(_))
+    ));
+}
+
+#[cfg(feature = "rdkafka")]
+#[test]
+fn rdkafka_message_round_trip() {
+    use rdkafka::message::{OwnedMessage, Timestamp};
+
+    let event = v1_0_event();
+    let record = to_binary_record(&event).unwrap();
+
+    let message = OwnedMessage::new(
+        record.value.clone(),
+        record.key.clone(),
+        "events".to_owned(),
+        Timestamp::NotAvailable,
+        0,
+        0,
+        Some(record.owned_headers()),
+    );
+    let consumed = KafkaRecord::from_message(&message);
+
+    assert_eq!(consumed, record);
+    assert_eq!(from_record(&consumed).unwrap(), event);
+}
@@ -0,0 +1,228 @@
+use cloudevents::message::{BuilderSink, EventDeserializer, EventSerializer};
+use cloudevents::v0_2::CloudEventV0_2Builder;
+use cloudevents::v1_0::CloudEventV1_0Builder;
+use cloudevents::{cloudevent_v0_2, cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{CloudEvent, Data, Error, ExtensionValue, SpecVersion};
+use std::collections::HashMap;
+
+/// Records every call, to check what the events feed to a serializer.
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl EventSerializer<Vec<String>> for Recorder {
+    fn set_spec_version(mut self, version: SpecVersion) -> Result<Self, Error> {
+        self.0.push(format!("specversion: {}", version));
+        Ok(self)
+    }
+
+    fn set_attribute(mut self, name: &str, value: String) -> Result<Self, Error> {
+        self.0.push(format!("attribute {}: {}", name, value));
+        Ok(self)
+    }
+
+    fn set_extension(mut self, name: &str, value: ExtensionValue) -> Result<Self, Error> {
+        self.0.push(format!("extension {}: {}", name, value));
+        Ok(self)
+    }
+
+    fn end_with_data(mut self, data: Data) -> Result<Vec<String>, Error> {
+        self.0.push(format!("data: {:?}", data));
+        Ok(self.0)
+    }
+
+    fn end(self) -> Result<Vec<String>, Error> {
+        Ok(self.0)
+    }
+}
+
+fn extensions() -> HashMap<String, ExtensionValue> {
+    let mut extensions = HashMap::new();
+    extensions.insert("zzz".to_owned(), ExtensionValue::from(true));
+    extensions.insert("aaa".to_owned(), ExtensionValue::from(42));
+    extensions
+}
+
+#[test]
+fn v1_0_event_is_walked_in_spec_order() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        time: "2019-12-04T18:33:09+00:00",
+        subject: "me",
+        dataschema: "http://www.google.com/schema.json",
+        datacontenttype: "text/plain",
+        data: Data::from_string("hello"),
+        extensions: extensions(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        event.deserialize_event(Recorder::default()).unwrap(),
+        vec![
+            "specversion: 1.0",
+            "attribute type: test type",
+            "attribute source: http://www.google.com",
+            "attribute id: id",
+            "attribute time: 2019-12-04T18:33:09Z",
+            "attribute subject: me",
+            "attribute dataschema: http://www.google.com/schema.json",
+            "attribute datacontenttype: text/plain",
+            "extension aaa: 42",
+            "extension zzz: true",
+            "data: StringOrBinary(\"hello\")",
+        ]
+    );
+}
+
+#[test]
+fn event_without_data_ends_without_data() {
+    let event = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        contenttype: "application/json",
+    )
+    .unwrap();
+
+    assert_eq!(
+        event.deserialize_event(Recorder::default()).unwrap(),
+        vec![
+            "specversion: 0.2",
+            "attribute type: test type",
+            "attribute source: http://www.google.com",
+            "attribute id: id",
+            "attribute contenttype: application/json",
+        ]
+    );
+}
+
+#[test]
+fn v0_3_binary_data_is_walked_without_encoding() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontentencoding: "base64",
+        data: Data::from_binary(b"bytes"),
+    )
+    .unwrap();
+
+    let calls = event.deserialize_event(Recorder::default()).unwrap();
+    assert!(!calls.iter().any(|c| c.contains("datacontentencoding")));
+    assert_eq!(
+        calls.last().unwrap(),
+        "data: Binary([98, 121, 116, 101, 115])"
+    );
+}
+
+#[test]
+fn events_are_rebuilt_with_the_builders() {
+    let events: Vec<CloudEvent> = vec![
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            time: "2019-12-04T18:33:09+00:00",
+            data: Data::from_binary(b"bytes"),
+            extensions: extensions(),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            subject: "me",
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            schemaurl: "http://www.google.com/schema.json",
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    for event in events {
+        assert_eq!(event.clone().into_event().unwrap(), event);
+    }
+}
+
+#[test]
+fn version_builder_is_a_serializer() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+    )
+    .unwrap();
+
+    let rebuilt = event
+        .clone()
+        .deserialize_event(CloudEventV1_0Builder::default())
+        .unwrap();
+    assert_eq!(rebuilt, event);
+}
+
+#[test]
+fn version_builder_rejects_other_spec_version() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+    )
+    .unwrap();
+
+    let error = event
+        .deserialize_event(CloudEventV0_2Builder::default())
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        Error::SpecVersionMismatch {
+            expected: SpecVersion::V0_2,
+            found: SpecVersion::V1_0
+        }
+    ));
+}
+
+#[test]
+fn builder_sink_rejects_unknown_attribute() {
+    let error = BuilderSink::default()
+        .set_spec_version(SpecVersion::V0_2)
+        .unwrap()
+        .set_attribute("subject", "me".to_owned())
+        .unwrap_err();
+
+    assert!(matches!(error, Error::UnknownAttribute(ref name) if name == "subject"));
+}
+
+#[test]
+fn builder_sink_requires_spec_version_first() {
+    let error = BuilderSink::default()
+        .set_attribute("id", "id".to_owned())
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::MissingRequiredAttribute("specversion")
+    ));
+}
+
+#[test]
+fn builder_sink_validates_the_event() {
+    let error = BuilderSink::default()
+        .set_spec_version(SpecVersion::V1_0)
+        .unwrap()
+        .set_attribute("type", "test type".to_owned())
+        .unwrap()
+        .set_attribute("source", "http://www.google.com".to_owned())
+        .unwrap()
+        .end()
+        .unwrap_err();
+
+    assert!(matches!(error, Error::MissingRequiredAttribute("id")));
+}
@@ -0,0 +1,157 @@
+mod common;
+
+use cloudevents::cloudevent_v0_3;
+use cloudevents::mqtt::{
+    from_publish, to_binary_publish, to_structured_publish, MqttPublish, MqttVersion,
+};
+use cloudevents::{CloudEvent, Error, ExtensionValue};
+use common::v1_0_event;
+
+fn property(name: &str, value: &str) -> (String, String) {
+    (name.to_owned(), value.to_owned())
+}
+
+#[test]
+fn binary_publish_has_user_properties() {
+    let publish = to_binary_publish(&v1_0_event()).unwrap();
+
+    assert_eq!(
+        publish.user_properties,
+        vec![
+            property("specversion", "1.0"),
+            property("type", "test type"),
+            property("source", "http://www.google.com"),
+            property("id", "id"),
+            property("time", "2019-12-04T18:33:09Z"),
+            property("subject", "me"),
+            property("comment", "hällo wörld"),
+        ]
+    );
+    assert_eq!(publish.content_type.as_deref(), Some("application/json"));
+    assert_eq!(publish.payload, br#"{"key":"value"}"#);
+}
+
+#[test]
+fn binary_publish_round_trip() {
+    let event = v1_0_event();
+    let publish = to_binary_publish(&event).unwrap();
+
+    assert_eq!(from_publish(&publish, MqttVersion::V5).unwrap(), event);
+}
+
+#[test]
+fn binary_publish_without_data_round_trip() {
+    let event: CloudEvent = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        subject: "me",
+    )
+    .unwrap()
+    .into();
+
+    let publish = to_binary_publish(&event).unwrap();
+    assert!(publish.payload.is_empty());
+    assert_eq!(from_publish(&publish, MqttVersion::V5).unwrap(), event);
+}
+
+#[test]
+fn v5_structured_publish_round_trip() {
+    let event = v1_0_event();
+    let publish = to_structured_publish(&event, MqttVersion::V5).unwrap();
+
+    assert_eq!(
+        publish.content_type.as_deref(),
+        Some("application/cloudevents+json")
+    );
+    assert!(publish.user_properties.is_empty());
+    assert_eq!(from_publish(&publish, MqttVersion::V5).unwrap(), event);
+}
+
+#[test]
+fn v3_1_1_publish_only_has_payload() {
+    let event = v1_0_event();
+    let publish = to_structured_publish(&event, MqttVersion::V3_1_1).unwrap();
+
+    assert_eq!(publish.content_type, None);
+    assert!(publish.user_properties.is_empty());
+    assert_eq!(from_publish(&publish, MqttVersion::V3_1_1).unwrap(), event);
+}
+
+#[test]
+fn v3_1_1_publish_is_always_structured() {
+    let publish = to_binary_publish(&v1_0_event()).unwrap();
+
+    assert!(matches!(
+        from_publish(&publish, MqttVersion::V3_1_1),
+        Err(Error::Json(_))
+    ));
+}
+
+#[test]
+fn user_property_with_any_name_is_read_as_extension() {
+    let publish = MqttPublish {
+        user_properties: vec![
+            property("specversion", "1.0"),
+            property("type", "test type"),
+            property("source", "http://www.google.com"),
+            property("id", "id"),
+            property("trace-id", "abc"),
+        ],
+        ..MqttPublish::default()
+    };
+
+    let event = match from_publish(&publish, MqttVersion::V5).unwrap() {
+        CloudEvent::V1_0(event) => event,
+        _ => unreachable!(),
+    };
+    assert_eq!(
+        event.extensions().unwrap()["trace-id"],
+        ExtensionValue::from("abc")
+    );
+}
+
+#[test]
+fn publish_without_spec_version_is_rejected() {
+    let publish = MqttPublish {
+        user_properties: vec![property("id", "id")],
+        ..MqttPublish::default()
+    };
+
+    assert!(matches!(
+        from_publish(&publish, MqttVersion::V5),
+        Err(Error::MissingRequiredAttribute("specversion"))
+    ));
+}
+
+#[test]
+fn batch_publish_is_rejected() {
+    let publish = MqttPublish {
+        content_type: Some("application/cloudevents-batch+json".to_owned()),
+        payload: b"[]".to_vec(),
+        ..MqttPublish::default()
+    };
+
+    assert!(matches!(
+        from_publish(&publish, MqttVersion::V5),
+        Err(Error::UnsupportedContentType(_))
+    ));
+}
+
+#[cfg(feature = "rumqttc")]
+#[test]
+fn rumqttc_publish_round_trip() {
+    let event = v1_0_event();
+
+    let publish = to_binary_publish(&event).unwrap();
+    let packet = publish.to_v5_publish("events", rumqttc::v5::mqttbytes::QoS::AtLeastOnce);
+    let received = MqttPublish::from_v5_publish(&packet);
+    assert_eq!(received, publish);
+    assert_eq!(from_publish(&received, MqttVersion::V5).unwrap(), event);
+
+    let publish = to_structured_publish(&event, MqttVersion::V3_1_1).unwrap();
+    let packet = publish.to_v3_1_1_publish("events", rumqttc::QoS::AtMostOnce);
+    assert_eq!(packet.topic, "events");
+    let received = MqttPublish::from_v3_1_1_publish(&packet);
+    assert_eq!(from_publish(&received, MqttVersion::V3_1_1).unwrap(), event);
+}
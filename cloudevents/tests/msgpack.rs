@@ -0,0 +1,74 @@
+#![cfg(feature = "msgpack")]
+
+mod common;
+
+use cloudevents::msgpack::{from_bytes, to_bytes};
+use cloudevents::{cloudevent_v0_2, cloudevent_v0_3, cloudevent_v1_0};
+use cloudevents::{Data, Error};
+use common::binary_event;
+use serde_json::json;
+
+fn contains(bytes: &[u8], part: &[u8]) -> bool {
+    bytes.windows(part.len()).any(|window| window == part)
+}
+
+#[test]
+fn binary_values_are_bin() {
+    let bytes = to_bytes(&binary_event()).unwrap();
+
+    // fixstr "data" followed by bin8 of 2 bytes
+    assert!(contains(&bytes, b"\xa4data\xc4\x02\x00\xff"));
+    // fixstr "key" followed by bin8 of 2 bytes
+    assert!(contains(&bytes, b"\xa3key\xc4\x02\x01\x02"));
+    assert!(!contains(&bytes, b"data_base64"));
+}
+
+#[test]
+fn events_round_trip() {
+    let events = vec![
+        binary_event(),
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            datacontenttype: "application/json",
+            data: Data::from_serializable(json!({"key": [1, "two", null]})).unwrap(),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            data: Data::from_binary(b"\x00\xff"),
+        )
+        .unwrap()
+        .into(),
+        cloudevent_v0_2!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            data: Data::from_binary(b"\x00\xff"),
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    for event in events {
+        assert_eq!(from_bytes(&to_bytes(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn invalid_input_is_rejected() {
+    assert!(matches!(
+        from_bytes(b"\xc1"),
+        Err(Error::MessagePackDecode(_))
+    ));
+
+    let bytes = rmp_serde::to_vec(&json!({"specversion": "1.0", "id": "id"})).unwrap();
+    assert!(matches!(
+        from_bytes(&bytes),
+        Err(Error::MessagePackDecode(_))
+    ));
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use cloudevents::nats::{from_message, to_binary_message, to_structured_message, NatsMessage};
+use cloudevents::{CloudEvent, CloudEventBuilder, Data, Error};
+use common::{v1_0_builder, v1_0_event};
+
+fn header(name: &str, value: &str) -> (String, String) {
+    (name.to_owned(), value.to_owned())
+}
+
+#[test]
+fn binary_message_has_ce_headers() {
+    let message = to_binary_message("events", &v1_0_event()).unwrap();
+
+    assert_eq!(message.subject, "events");
+    assert_eq!(
+        message.headers,
+        vec![
+            header("ce-specversion", "1.0"),
+            header("ce-type", "test type"),
+            header("ce-source", "http://www.google.com"),
+            header("ce-id", "id"),
+            header("ce-time", "2019-12-04T18:33:09Z"),
+            header("ce-subject", "me"),
+            header("content-type", "application/json"),
+            header("ce-comment", "hällo wörld"),
+        ]
+    );
+    assert_eq!(message.payload, br#"{"key":"value"}"#);
+}
+
+#[test]
+fn binary_message_round_trip() {
+    let event = v1_0_event();
+    let message = to_binary_message("events", &event).unwrap();
+
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn v0_2_event_round_trip() {
+    let event: CloudEvent = CloudEventBuilder::v0_2()
+        .event_type("test type")
+        .source("http://www.google.com")
+        .event_id("id")
+        .build()
+        .unwrap()
+        .into();
+
+    let message = to_structured_message("events", &event).unwrap();
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn structured_message_round_trip() {
+    let event = v1_0_event();
+    let message = to_structured_message("events", &event).unwrap();
+
+    assert_eq!(
+        message.headers,
+        vec![header("content-type", "application/cloudevents+json")]
+    );
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn message_without_headers_is_structured() {
+    let event = v1_0_event();
+    let message = NatsMessage {
+        subject: "events".to_owned(),
+        headers: Vec::new(),
+        payload: serde_json::to_vec(&event).unwrap(),
+    };
+
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn headers_are_case_insensitive() {
+    let message = NatsMessage {
+        headers: vec![
+            header("CE-SpecVersion", "1.0"),
+            header("Ce-Type", "test type"),
+            header("Ce-Source", "http://www.google.com"),
+            header("Ce-Id", "id"),
+            header("Content-Type", "text/plain"),
+        ],
+        payload: b"hello".to_vec(),
+        ..NatsMessage::default()
+    };
+
+    let expected: CloudEvent = CloudEventBuilder::v1_0()
+        .event_type("test type")
+        .source("http://www.google.com")
+        .event_id("id")
+        .datacontenttype("text/plain")
+        .data(Data::from_string("hello"))
+        .build()
+        .unwrap()
+        .into();
+    assert_eq!(from_message(&message).unwrap(), expected);
+}
+
+#[test]
+fn message_with_other_headers_is_structured() {
+    let event = v1_0_event();
+    let message = NatsMessage {
+        subject: "events".to_owned(),
+        headers: vec![header("Nats-Msg-Id", "1")],
+        payload: serde_json::to_vec(&event).unwrap(),
+    };
+
+    assert_eq!(from_message(&message).unwrap(), event);
+}
+
+#[test]
+fn message_without_spec_version_is_not_binary() {
+    let message = NatsMessage {
+        headers: vec![header("ce-id", "id")],
+        ..NatsMessage::default()
+    };
+
+    assert!(matches!(from_message(&message), Err(Error::Json(_))));
+}
+
+#[test]
+fn values_with_line_breaks_are_rejected() {
+    let event: CloudEvent = v1_0_builder().subject("a\r\nb").build().unwrap().into();
+    assert!(matches!(
+        to_binary_message("events", &event),
+        Err(Error::InvalidAttributeValue(ref name)) if name == "subject"
+    ));
+
+    let event: CloudEvent = v1_0_builder()
+        .extension("comment", "a\nb")
+        .build()
+        .unwrap()
+        .into();
+    assert!(matches!(
+        to_binary_message("events", &event),
+        Err(Error::InvalidExtensionValue(ref name)) if name == "comment"
+    ));
+    assert_eq!(
+        from_message(&to_structured_message("events", &event).unwrap()).unwrap(),
+        event
+    );
+}
@@ -0,0 +1,218 @@
+#![cfg(feature = "protobuf")]
+
+mod common;
+
+use cloudevents::cloudevent_v1_0;
+use cloudevents::protobuf::proto::cloud_event::cloud_event_attribute_value::Attr;
+use cloudevents::protobuf::proto::cloud_event::{self, CloudEventAttributeValue};
+use cloudevents::protobuf::proto::{self, CloudEventBatch};
+use cloudevents::protobuf::{batch_from_bytes, batch_to_bytes, from_bytes, to_bytes};
+use cloudevents::v1_0::CloudEventV1_0;
+use cloudevents::{Data, Error, ExtensionValue};
+use common::typed_extensions;
+use prost::Message;
+use serde_json::json;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+fn event() -> CloudEventV1_0 {
+    cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        time: "2019-12-04T18:33:09.5Z",
+        subject: "me",
+        dataschema: "http://www.google.com/schema.json",
+        datacontenttype: "application/json",
+        data: Data::from_serializable(json!({"key": "value"})).unwrap(),
+        extensions: typed_extensions(),
+    )
+    .unwrap()
+}
+
+fn attr(message: &proto::CloudEvent, name: &str) -> Attr {
+    message.attributes[name].attr.clone().unwrap()
+}
+
+#[test]
+fn event_is_mapped_to_message() {
+    let message = proto::CloudEvent::try_from(event()).unwrap();
+
+    assert_eq!(message.id, "id");
+    assert_eq!(message.source, "http://www.google.com");
+    assert_eq!(message.spec_version, "1.0");
+    assert_eq!(message.r#type, "test type");
+    assert_eq!(
+        attr(&message, "time"),
+        Attr::CeTimestamp(prost_types::Timestamp {
+            seconds: 1_575_484_389,
+            nanos: 500_000_000,
+        })
+    );
+    assert_eq!(attr(&message, "subject"), Attr::CeString("me".to_owned()));
+    assert_eq!(
+        attr(&message, "dataschema"),
+        Attr::CeUri("http://www.google.com/schema.json".to_owned())
+    );
+    assert_eq!(attr(&message, "boolean"), Attr::CeBoolean(true));
+    assert_eq!(attr(&message, "integer"), Attr::CeInteger(42));
+    assert_eq!(attr(&message, "binary"), Attr::CeBytes(b"bytes".to_vec()));
+    assert_eq!(attr(&message, "uriref"), Attr::CeUriRef("/path".to_owned()));
+    assert_eq!(
+        message.data,
+        Some(cloud_event::Data::TextData(r#"{"key":"value"}"#.to_owned()))
+    );
+}
+
+#[test]
+fn event_round_trip() {
+    let event = event();
+
+    assert_eq!(from_bytes(&to_bytes(&event).unwrap()).unwrap(), event);
+}
+
+#[test]
+fn binary_and_text_data_round_trip() {
+    let events = vec![
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            data: Data::from_binary(b"bytes"),
+        )
+        .unwrap(),
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            datacontenttype: "text/plain",
+            data: Data::from_string("hello"),
+        )
+        .unwrap(),
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+        )
+        .unwrap(),
+    ];
+
+    for event in events {
+        assert_eq!(from_bytes(&to_bytes(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn protobuf_data_is_carried_as_any() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontenttype: "application/protobuf",
+        dataschema: "https://type.googleapis.com/example.Message",
+        data: Data::from_binary(b"\x08\x01"),
+    )
+    .unwrap();
+
+    let message = proto::CloudEvent::try_from(event.clone()).unwrap();
+    assert_eq!(
+        message.data,
+        Some(cloud_event::Data::ProtoData(prost_types::Any {
+            type_url: "https://type.googleapis.com/example.Message".to_owned(),
+            value: b"\x08\x01".to_vec(),
+        }))
+    );
+    assert_eq!(CloudEventV1_0::try_from(message).unwrap(), event);
+}
+
+#[test]
+fn any_populates_missing_attributes() {
+    let message = |type_url: &str| proto::CloudEvent {
+        id: "id".to_owned(),
+        source: "http://www.google.com".to_owned(),
+        spec_version: "1.0".to_owned(),
+        r#type: "test type".to_owned(),
+        attributes: HashMap::new(),
+        data: Some(cloud_event::Data::ProtoData(prost_types::Any {
+            type_url: type_url.to_owned(),
+            value: b"\x08\x01".to_vec(),
+        })),
+    };
+
+    let event =
+        CloudEventV1_0::try_from(message("https://type.googleapis.com/example.Message")).unwrap();
+    assert_eq!(event.datacontenttype(), Some("application/protobuf"));
+    assert_eq!(
+        event.dataschema(),
+        Some("https://type.googleapis.com/example.Message")
+    );
+    assert_eq!(event.data(), Some(&Data::from_binary(b"\x08\x01")));
+
+    let event = CloudEventV1_0::try_from(message("type.googleapis.com/example.Message")).unwrap();
+    assert_eq!(event.datacontenttype(), Some("application/protobuf"));
+    assert_eq!(event.dataschema(), None);
+}
+
+#[test]
+fn batch_round_trip() {
+    let events = vec![
+        event(),
+        cloudevent_v1_0!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "2",
+        )
+        .unwrap(),
+    ];
+
+    let bytes = batch_to_bytes(&events).unwrap();
+    assert_eq!(CloudEventBatch::decode(&bytes[..]).unwrap().events.len(), 2);
+    assert_eq!(batch_from_bytes(&bytes).unwrap(), events);
+}
+
+#[test]
+fn large_integer_extension_is_rejected() {
+    let mut extensions = HashMap::new();
+    extensions.insert("big".to_owned(), ExtensionValue::Integer(i64::MAX));
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        extensions: extensions,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        to_bytes(&event),
+        Err(Error::InvalidExtensionValue(ref name)) if name == "big"
+    ));
+}
+
+#[test]
+fn mistyped_attribute_is_rejected() {
+    let mut attributes = HashMap::new();
+    attributes.insert(
+        "time".to_owned(),
+        CloudEventAttributeValue {
+            attr: Some(Attr::CeBoolean(true)),
+        },
+    );
+    let message = proto::CloudEvent {
+        id: "id".to_owned(),
+        source: "http://www.google.com".to_owned(),
+        spec_version: "1.0".to_owned(),
+        r#type: "test type".to_owned(),
+        attributes,
+        data: None,
+    };
+
+    assert!(matches!(
+        CloudEventV1_0::try_from(message),
+        Err(Error::InvalidAttributeValue(ref name)) if name == "time"
+    ));
+}
+
+#[test]
+fn invalid_bytes_are_rejected() {
+    assert!(matches!(from_bytes(b"\xff\xff"), Err(Error::Protobuf(_))));
+}
@@ -1,7 +1,9 @@
 use cloudevents::cloudevent_v0_2;
+use cloudevents::v0_2::CloudEventV0_2;
 use cloudevents::{CloudEventBuilder, Data, ExtensionValue};
 use serde_derive::Serialize;
 use serde_json::json;
+use std::collections::HashMap;
 
 #[test]
 fn string_data_can_be_created_from_str() {
@@ -13,10 +15,7 @@ fn string_data_can_be_created_from_str() {
 #[test]
 fn binary_data_can_be_created_from_slice() {
     let data = Data::from_binary(b"this is binary");
-    assert_eq!(
-        data,
-        Data::StringOrBinary("dGhpcyBpcyBiaW5hcnk=".to_owned())
-    )
+    assert_eq!(data, Data::Binary(b"this is binary".to_vec()))
 }
 
 #[test]
@@ -150,3 +149,78 @@ fn serialize() {
     let json = serde_json::to_string(&event.unwrap()).unwrap();
     assert_eq!(json, "{\"type\":\"test type\",\"specversion\":\"0.2\",\"source\":\"http://www.google.com\",\"id\":\"id\",\"contenttype\":\"application/json\",\"data\":\"\\\"test\\\"\"}");
 }
+
+#[test]
+fn serialize_extensions_as_top_level_members() {
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexampleextension".to_owned(),
+        ExtensionValue::from_string("value"),
+    );
+    let event = cloudevent_v0_2!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        extensions: extensions,
+    );
+
+    let json = serde_json::to_value(event.unwrap()).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "type": "test type",
+            "specversion": "0.2",
+            "source": "http://www.google.com",
+            "id": "id",
+            "comexampleextension": "value",
+        })
+    );
+}
+
+#[test]
+fn deserialize_unknown_members_as_extensions() {
+    let event: CloudEventV0_2 = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "0.2",
+        "source": "http://www.google.com",
+        "id": "id",
+        "contenttype": "application/json",
+        "comexampleextension": "value",
+    }))
+    .unwrap();
+
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexampleextension".to_owned(),
+        ExtensionValue::from_string("value"),
+    );
+    assert_eq!(event.contenttype(), Some("application/json"));
+    assert_eq!(event.extensions(), Some(&extensions));
+}
+
+#[test]
+fn deserialize_rejects_other_specversion() {
+    let result = serde_json::from_value::<CloudEventV0_2>(json!({
+        "type": "test type",
+        "specversion": "0.1",
+        "source": "http://www.google.com",
+        "id": "id",
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn all_validation_errors_are_collected() {
+    let errors = CloudEventBuilder::v0_2()
+        .schemaurl("http://www.google.com:port/schema.json")
+        .time("yesterday")
+        .build_collecting_errors()
+        .unwrap_err();
+
+    let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    assert_eq!(
+        attributes,
+        vec!["type", "source", "id", "time", "schemaurl"]
+    );
+}
@@ -0,0 +1,150 @@
+use cloudevents::cloudevent_v0_3;
+use cloudevents::get_event_field;
+use cloudevents::v0_3::CloudEventV0_3;
+use cloudevents::{CloudEvent, CloudEventBuilder, Data};
+
+#[test]
+fn builder_works() {
+    let event = CloudEventBuilder::v0_3()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .datacontenttype("application/json")
+        .build()
+        .unwrap();
+
+    assert_eq!(event.event_type(), "test type");
+    assert_eq!(event.source(), "http://www.google.com");
+    assert_eq!(event.event_id(), "id");
+    assert_eq!(event.extensions(), None);
+    assert_eq!(event.data(), None);
+    assert_eq!(event.event_time(), None);
+    assert_eq!(event.subject(), None);
+    assert_eq!(event.datacontenttype(), Some("application/json"));
+    assert_eq!(event.datacontentencoding(), None);
+    assert_eq!(event.schema_url(), None);
+}
+
+#[test]
+fn builder_macro_works() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        subject: "me",
+        schemaurl: "https://lol.org/schema.json",
+        datacontenttype: "text/plain",
+        datacontentencoding: "base64",
+        data: Data::from_string("dGVzdA=="),
+    )
+    .unwrap();
+
+    assert_eq!(event.event_type(), "test type");
+    assert_eq!(event.source(), "http://www.google.com");
+    assert_eq!(event.event_id(), "id");
+    assert_eq!(event.extensions(), None);
+    assert_eq!(
+        event.data(),
+        Some(&Data::StringOrBinary("dGVzdA==".to_owned()))
+    );
+    assert_eq!(event.event_time(), None);
+    assert_eq!(event.subject(), Some("me"));
+    assert_eq!(event.datacontenttype(), Some("text/plain"));
+    assert_eq!(event.datacontentencoding(), Some("base64"));
+    assert_eq!(event.schema_url(), Some("https://lol.org/schema.json"));
+}
+
+#[test]
+fn source_is_allowed_to_be_a_relative_uri() {
+    let event = CloudEventBuilder::v0_3()
+        .event_id("id")
+        .source("/cloudevents/spec/pull/123")
+        .event_type("test type")
+        .build()
+        .unwrap();
+
+    assert_eq!(event.source(), "/cloudevents/spec/pull/123");
+}
+
+#[test]
+fn source_is_allowed_to_be_a_urn() {
+    let event = CloudEventBuilder::v0_3()
+        .event_id("id")
+        .source("urn:event:from:myapi/resourse/123")
+        .event_type("test type")
+        .build()
+        .unwrap();
+
+    assert_eq!(event.source(), "urn:event:from:myapi/resourse/123");
+}
+
+#[test]
+fn invalid_time_is_rejected() {
+    let event = CloudEventBuilder::v0_3()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .time("yesterday")
+        .build();
+
+    assert!(event.is_err());
+}
+
+#[test]
+fn event_field_can_be_read_from_wrapped_event() {
+    let event = CloudEvent::V0_3(
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+        )
+        .unwrap(),
+    );
+
+    assert_eq!(get_event_field!(event, event_id), "id");
+}
+
+#[test]
+fn serialize() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontenttype: "application/json",
+        data: Data::from_string("\"test\""),
+    );
+
+    let json = serde_json::to_string(&event.unwrap()).unwrap();
+    assert_eq!(json, "{\"type\":\"test type\",\"specversion\":\"0.3\",\"source\":\"http://www.google.com\",\"id\":\"id\",\"datacontenttype\":\"application/json\",\"data\":\"\\\"test\\\"\"}");
+}
+
+#[test]
+fn serialize_binary_data_with_base64_datacontentencoding() {
+    let event = cloudevent_v0_3!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        data: Data::from_binary(b"this is binary"),
+    )
+    .unwrap();
+
+    assert_eq!(event.datacontentencoding(), Some("base64"));
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["datacontentencoding"], "base64");
+    assert_eq!(json["data"], "dGhpcyBpcyBiaW5hcnk=");
+
+    let deserialized: CloudEventV0_3 = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, event);
+}
+
+#[test]
+fn base64_datacontentencoding_is_case_insensitive() {
+    let event: CloudEventV0_3 = serde_json::from_str(
+        r#"{"type":"test type","specversion":"0.3","source":"http://www.google.com","id":"id","datacontentencoding":"BASE64","data":"dGhpcyBpcyBiaW5hcnk="}"#,
+    )
+    .unwrap();
+
+    assert_eq!(event.data(), Some(&Data::from_binary(b"this is binary")));
+    assert_eq!(event.datacontentencoding(), Some("BASE64"));
+}
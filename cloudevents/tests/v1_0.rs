@@ -1,8 +1,9 @@
 use cloudevents::cloudevent_v1_0;
-use cloudevents::v1_0::CloudEventV1_0Builder;
-use cloudevents::{Data, ExtensionValue};
+use cloudevents::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use cloudevents::{Data, Error, ExtensionValue, Validate};
 use serde_derive::Serialize;
 use serde_json::json;
+use std::collections::HashMap;
 
 #[test]
 fn string_data_can_be_created_from_str() {
@@ -14,10 +15,7 @@ fn string_data_can_be_created_from_str() {
 #[test]
 fn binary_data_can_be_created_from_slice() {
     let data = Data::from_binary(b"this is binary");
-    assert_eq!(
-        data,
-        Data::StringOrBinary("dGhpcyBpcyBiaW5hcnk=".to_owned())
-    )
+    assert_eq!(data, Data::Binary(b"this is binary".to_vec()))
 }
 
 #[test]
@@ -81,6 +79,61 @@ fn builder_works() {
     assert_eq!(event.dataschema(), None);
 }
 
+#[test]
+fn typed_data_setters_set_datacontenttype() {
+    let builder = || {
+        CloudEventV1_0Builder::default()
+            .event_id("id")
+            .source("http://www.google.com")
+            .event_type("test type")
+    };
+
+    let event = builder()
+        .json_data(json!({"a": 1}))
+        .unwrap()
+        .build()
+        .unwrap();
+    assert_eq!(event.data(), Some(&Data::Object(json!({"a": 1}))));
+    assert_eq!(event.datacontenttype(), Some("application/json"));
+
+    let event = builder().text_data("hello", "text/plain").build().unwrap();
+    assert_eq!(event.data(), Some(&Data::from_string("hello")));
+    assert_eq!(event.datacontenttype(), Some("text/plain"));
+
+    let event = builder()
+        .binary_data(b"\x89PNG", "image/png")
+        .build()
+        .unwrap();
+    assert_eq!(event.data(), Some(&Data::from_binary(b"\x89PNG")));
+    assert_eq!(event.datacontenttype(), Some("image/png"));
+}
+
+#[test]
+fn json_data_with_other_datacontenttype_is_rejected() {
+    let error = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .data(Data::from_serializable(json!({"a": 1})).unwrap())
+        .datacontenttype("text/plain")
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::DataContentTypeMismatch { attribute: "datacontenttype", ref value } if value == "text/plain"
+    ));
+
+    let event = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .data(Data::from_serializable(json!({"a": 1})).unwrap())
+        .datacontenttype("application/cloudevents+json; charset=utf-8")
+        .build();
+    assert!(event.is_ok());
+}
+
 #[test]
 fn builder_macro_works() {
     let event = cloudevent_v1_0!(
@@ -151,3 +204,195 @@ fn serialize() {
     let json = serde_json::to_string(&event.unwrap()).unwrap();
     assert_eq!(json, "{\"type\":\"test type\",\"specversion\":\"1.0\",\"source\":\"http://www.google.com\",\"id\":\"id\",\"datacontenttype\":\"application/json\",\"data\":\"\\\"test\\\"\"}");
 }
+
+#[test]
+fn serialize_extensions_as_top_level_members() {
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexampleextension".to_owned(),
+        ExtensionValue::from_string("value"),
+    );
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        extensions: extensions,
+    );
+
+    let json = serde_json::to_value(event.unwrap()).unwrap();
+    assert_eq!(
+        json,
+        json!({
+            "type": "test type",
+            "specversion": "1.0",
+            "source": "http://www.google.com",
+            "id": "id",
+            "comexampleextension": "value",
+        })
+    );
+}
+
+#[test]
+fn deserialize_unknown_members_as_extensions() {
+    let event: CloudEventV1_0 = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+        "id": "id",
+        "datacontenttype": "application/json",
+        "comexampleextension": "value",
+    }))
+    .unwrap();
+
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexampleextension".to_owned(),
+        ExtensionValue::from_string("value"),
+    );
+    assert_eq!(event.datacontenttype(), Some("application/json"));
+    assert_eq!(event.extensions(), Some(&extensions));
+}
+
+#[test]
+fn deserialize_rejects_other_specversion() {
+    let result = serde_json::from_value::<CloudEventV1_0>(json!({
+        "type": "test type",
+        "specversion": "0.1",
+        "source": "http://www.google.com",
+        "id": "id",
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn serialize_binary_data_as_data_base64() {
+    let event = cloudevent_v1_0!(
+        event_type: "test type",
+        source: "http://www.google.com",
+        event_id: "id",
+        datacontenttype: "application/octet-stream",
+        data: Data::from_binary(b"this is binary"),
+    )
+    .unwrap();
+
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(json["data_base64"], "dGhpcyBpcyBiaW5hcnk=");
+    assert_eq!(json.get("data"), None);
+
+    let deserialized: CloudEventV1_0 = serde_json::from_value(json).unwrap();
+    assert_eq!(deserialized, event);
+}
+
+#[test]
+fn deserialize_rejects_data_and_data_base64() {
+    let result = serde_json::from_value::<CloudEventV1_0>(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+        "id": "id",
+        "data": "test",
+        "data_base64": "dGVzdA==",
+    }));
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn nested_extension_values_are_rejected() {
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexampleextension".to_owned(),
+        ExtensionValue::from_serializable(json!({"nested": true})).unwrap(),
+    );
+    let event = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .extensions(extensions)
+        .build();
+
+    assert!(event.is_err());
+}
+
+#[test]
+fn missing_attribute_is_reported() {
+    let error = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(error, Error::MissingRequiredAttribute("type")));
+}
+
+#[test]
+fn invalid_source_is_reported() {
+    let error = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com:port")
+        .event_type("test type")
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(
+        error,
+        Error::InvalidUri {
+            attribute: "source",
+            ..
+        }
+    ));
+}
+
+#[test]
+fn invalid_time_is_reported() {
+    let error = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .time("yesterday")
+        .build()
+        .unwrap_err();
+
+    assert!(matches!(error, Error::InvalidTime { .. }));
+}
+
+#[test]
+fn invalid_extension_name_is_only_reported_by_validate() {
+    let event = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .extension("trace-id", "value")
+        .build()
+        .unwrap();
+
+    let errors = event.validate().unwrap_err();
+    assert!(matches!(errors[0], Error::InvalidExtensionName(ref name) if name == "trace-id"));
+}
+
+#[test]
+fn all_validation_errors_are_collected() {
+    let mut extensions = HashMap::new();
+    extensions.insert(
+        "comexample".to_owned(),
+        ExtensionValue::from_serializable(json!({"nested": true})).unwrap(),
+    );
+    extensions.insert(
+        "array".to_owned(),
+        ExtensionValue::from_serializable(json!([1, 2])).unwrap(),
+    );
+    let errors = CloudEventV1_0Builder::default()
+        .event_id("id")
+        .source("http://www.google.com:port")
+        .dataschema("http://www.google.com:port/schema.json")
+        .extensions(extensions)
+        .build_collecting_errors()
+        .unwrap_err();
+
+    let attributes: Vec<_> = errors.iter().filter_map(|e| e.attribute()).collect();
+    assert_eq!(
+        attributes,
+        vec!["type", "source", "dataschema", "array", "comexample"]
+    );
+}
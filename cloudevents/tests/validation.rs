@@ -0,0 +1,159 @@
+use cloudevents::v0_2::CloudEventV0_2;
+use cloudevents::v0_3::CloudEventV0_3;
+use cloudevents::v1_0::CloudEventV1_0;
+use cloudevents::{CloudEvent, CloudEventBuilder, Error, Validate, Validated};
+use serde_json::json;
+
+fn attributes(errors: &[Error]) -> Vec<&str> {
+    errors.iter().filter_map(|e| e.attribute()).collect()
+}
+
+#[test]
+fn valid_events_pass_validation() {
+    for specversion in &["0.2", "0.3", "1.0"] {
+        let event: CloudEvent = serde_json::from_value(json!({
+            "type": "test type",
+            "specversion": specversion,
+            "source": "/sensors/1",
+            "id": "id",
+        }))
+        .unwrap();
+
+        assert!(event.validate().is_ok());
+    }
+}
+
+#[test]
+fn v1_0_violations_are_reported() {
+    let event: CloudEventV1_0 = serde_json::from_value(json!({
+        "type": "",
+        "specversion": "1.0",
+        "source": "http://www.google.com:port",
+        "id": "",
+        "subject": "",
+        "dataschema": "/schema.json",
+        "datacontenttype": "json",
+        "Com_Example": "value",
+        "nested": {"a": 1},
+    }))
+    .unwrap();
+
+    let errors = event.validate().unwrap_err();
+    assert_eq!(
+        attributes(&errors),
+        vec![
+            "type",
+            "source",
+            "id",
+            "subject",
+            "dataschema",
+            "datacontenttype",
+            "Com_Example",
+            "nested"
+        ]
+    );
+}
+
+#[test]
+fn v1_0_extension_values_outside_the_type_system_are_reported() {
+    let event: CloudEventV1_0 = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "/sensors/1",
+        "id": "id",
+        "big": 2_147_483_648i64,
+        "double": 1.5,
+        "empty": null,
+        "flag": true,
+        "small": -2_147_483_648i64,
+    }))
+    .unwrap();
+
+    let errors = event.validate().unwrap_err();
+    assert_eq!(attributes(&errors), vec!["big", "double", "empty"]);
+}
+
+#[test]
+fn v0_3_violations_are_reported() {
+    let event: CloudEventV0_3 = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "0.3",
+        "source": "http://www.google.com",
+        "id": "id",
+        "schemaurl": "schema.json",
+        "datacontenttype": "text/plain; charset",
+        "datacontentencoding": "gzip",
+        "thisextensionnameistoolong": "value",
+    }))
+    .unwrap();
+
+    let errors = event.validate().unwrap_err();
+    assert_eq!(
+        attributes(&errors),
+        vec![
+            "schemaurl",
+            "datacontenttype",
+            "datacontentencoding",
+            "thisextensionnameistoolong"
+        ]
+    );
+}
+
+#[test]
+fn v0_2_violations_are_reported() {
+    let event: CloudEventV0_2 = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "0.2",
+        "source": "",
+        "id": "id",
+        "contenttype": "application/",
+    }))
+    .unwrap();
+
+    let errors = event.validate().unwrap_err();
+    assert_eq!(attributes(&errors), vec!["source", "contenttype"]);
+    assert!(matches!(errors[0], Error::EmptyAttribute("source")));
+}
+
+#[test]
+fn media_types_with_parameters_are_accepted() {
+    let event = CloudEventBuilder::v1_0()
+        .event_id("id")
+        .source("http://www.google.com")
+        .event_type("test type")
+        .datacontenttype("text/plain; charset=utf-8")
+        .build();
+
+    assert!(event.is_ok());
+}
+
+#[test]
+fn validated_deserializer_accepts_valid_event() {
+    let event: Validated<CloudEvent> = serde_json::from_value(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+        "id": "id",
+    }))
+    .unwrap();
+
+    assert_eq!(event.into_inner().spec_version().as_str(), "1.0");
+}
+
+#[test]
+fn validated_deserializer_reports_all_violations() {
+    let error = serde_json::from_value::<Validated<CloudEventV1_0>>(json!({
+        "type": "test type",
+        "specversion": "1.0",
+        "source": "http://www.google.com",
+        "id": "",
+        "dataschema": "/schema.json",
+    }))
+    .unwrap_err();
+
+    assert_eq!(
+        error.to_string(),
+        "Attribute id must not be empty; \
+         Attribute dataschema is not a valid URI: relative URL without a base"
+    );
+}
@@ -0,0 +1,72 @@
+mod common;
+
+use cloudevents::cloudevent_v0_3;
+use cloudevents::websocket::{
+    from_frame, negotiate_subprotocol, to_frame, WebSocketFrame, SUBPROTOCOL_JSON,
+};
+use cloudevents::{Data, Error};
+use common::v1_0_event;
+use serde_json::json;
+
+#[test]
+fn event_is_framed_as_json_text() {
+    let frame = to_frame(&v1_0_event()).unwrap();
+
+    match frame {
+        WebSocketFrame::Text(text) => {
+            let value: serde_json::Value = serde_json::from_str(&text).unwrap();
+            assert_eq!(value["specversion"], "1.0");
+            assert_eq!(value["data"], json!({"key": "value"}));
+        }
+        WebSocketFrame::Binary(_) => panic!("expected a text frame"),
+    }
+}
+
+#[test]
+fn frames_round_trip() {
+    let events = vec![
+        v1_0_event(),
+        cloudevent_v0_3!(
+            event_type: "test type",
+            source: "http://www.google.com",
+            event_id: "id",
+            datacontentencoding: "base64",
+            data: Data::from_binary(b"bytes"),
+        )
+        .unwrap()
+        .into(),
+    ];
+
+    for event in events {
+        assert_eq!(from_frame(&to_frame(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn binary_frame_is_read() {
+    let event = v1_0_event();
+    let frame = WebSocketFrame::Binary(serde_json::to_vec(&event).unwrap());
+
+    assert_eq!(from_frame(&frame).unwrap(), event);
+}
+
+#[test]
+fn invalid_frame_is_rejected() {
+    let frame = WebSocketFrame::Text(r#"{"specversion": "1.0"}"#.to_owned());
+
+    assert!(matches!(from_frame(&frame), Err(Error::Json(_))));
+}
+
+#[test]
+fn subprotocol_is_negotiated() {
+    assert_eq!(
+        negotiate_subprotocol(SUBPROTOCOL_JSON),
+        Some("cloudevents.json")
+    );
+    assert_eq!(
+        negotiate_subprotocol("cloudevents.proto,  cloudevents.json ,chat"),
+        Some("cloudevents.json")
+    );
+    assert_eq!(negotiate_subprotocol("cloudevents.avro, chat"), None);
+    assert_eq!(negotiate_subprotocol(""), None);
+}
@@ -0,0 +1,232 @@
+#![cfg(feature = "xml")]
+
+mod common;
+
+use cloudevents::cloudevent_v1_0;
+use cloudevents::v1_0::{CloudEventV1_0, CloudEventV1_0Builder};
+use cloudevents::xml::{from_str, to_string};
+use cloudevents::{Data, Error, ExtensionValue};
+use common::typed_extensions;
+use serde_json::json;
+use std::collections::HashMap;
+
+fn event() -> CloudEventV1_0 {
+    cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        time: "2020-01-01T00:00:00Z",
+        subject: "me",
+        datacontenttype: "text/plain",
+        data: Data::from_string("hello & goodbye"),
+        extensions: typed_extensions(),
+    )
+    .unwrap()
+}
+
+const FIXTURE: &[&str] = &[
+    r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+    r#"<ce:event xmlns:ce="http://cloudevents.io/xmlformat/V1" xmlns:xs="http://www.w3.org/2001/XMLSchema" xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" specversion="1.0">"#,
+    "<ce:type>t</ce:type>",
+    "<ce:source>/s</ce:source>",
+    "<ce:id>1</ce:id>",
+    "<ce:time>2020-01-01T00:00:00Z</ce:time>",
+    "<ce:subject>me</ce:subject>",
+    "<ce:datacontenttype>text/plain</ce:datacontenttype>",
+    r#"<ce:binary xsi:type="xs:base64Binary">Ynl0ZXM=</ce:binary>"#,
+    r#"<ce:boolean xsi:type="xs:boolean">true</ce:boolean>"#,
+    r#"<ce:integer xsi:type="xs:int">42</ce:integer>"#,
+    r#"<ce:string xsi:type="xs:string">a &lt; b</ce:string>"#,
+    r#"<ce:timestamp xsi:type="xs:dateTime">2019-12-04T18:33:09.123456789Z</ce:timestamp>"#,
+    r#"<ce:uri xsi:type="xs:anyURI">http://www.google.com/</ce:uri>"#,
+    r#"<ce:uriref xsi:type="xs:anyURI">/path</ce:uriref>"#,
+    "<ce:dataText>hello &amp; goodbye</ce:dataText>",
+    "</ce:event>",
+];
+
+#[test]
+fn event_matches_fixture() {
+    assert_eq!(to_string(&event()).unwrap(), FIXTURE.concat());
+    assert_eq!(from_str(&FIXTURE.concat()).unwrap(), event());
+}
+
+#[test]
+fn data_round_trip() {
+    let data = vec![
+        (None, None),
+        (None, Some(Data::from_binary(b"\x00\xff"))),
+        (
+            Some("application/json"),
+            Some(Data::from_serializable(json!({"key": ["value", 1]})).unwrap()),
+        ),
+        (
+            Some("application/xml"),
+            Some(Data::from_string("<a><b>c</b><d/></a>")),
+        ),
+        (Some("text/xml"), Some(Data::from_string("not <xml>"))),
+        (
+            Some("application/atom+xml"),
+            Some(Data::from_string("<a/><b/>")),
+        ),
+    ];
+
+    for (datacontenttype, data) in data {
+        let mut builder = CloudEventV1_0Builder::default()
+            .event_type("t")
+            .source("/s")
+            .event_id("1");
+        if let Some(datacontenttype) = datacontenttype {
+            builder = builder.datacontenttype(datacontenttype);
+        }
+        if let Some(data) = data {
+            builder = builder.data(data);
+        }
+        let event = builder.build().unwrap();
+
+        assert_eq!(from_str(&to_string(&event).unwrap()).unwrap(), event);
+    }
+}
+
+#[test]
+fn xml_data_is_embedded() {
+    let event = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        datacontenttype: "application/xml; charset=utf-8",
+        data: Data::from_string("<?xml version=\"1.0\"?>\n<order id=\"1\">&amp;</order>"),
+    )
+    .unwrap();
+
+    let xml = to_string(&event).unwrap();
+    assert!(xml.ends_with(r#"<ce:dataXml><order id="1">&amp;</order></ce:dataXml></ce:event>"#));
+    assert_eq!(
+        from_str(&xml).unwrap().data(),
+        Some(&Data::from_string(r#"<order id="1">&amp;</order>"#))
+    );
+}
+
+#[test]
+fn foreign_document_is_read() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+        <!-- an event -->
+        <event xmlns="http://cloudevents.io/xmlformat/V1"
+               xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+               xmlns:s="http://www.w3.org/2001/XMLSchema"
+               specversion="1.0">
+            <id>1</id>
+            <source>/s</source>
+            <type>t</type>
+            <empty xsi:type="s:string"/>
+            <ext xsi:type="s:int"> 7 </ext>
+            <other>plain</other>
+            <datacontenttype>application/json</datacontenttype>
+            <dataText><![CDATA[{"a": "<b>"}]]></dataText>
+        </event>
+    "#;
+
+    let mut extensions = HashMap::new();
+    extensions.insert("ext".to_owned(), ExtensionValue::from(7));
+    extensions.insert("other".to_owned(), ExtensionValue::from("plain"));
+    extensions.insert("empty".to_owned(), ExtensionValue::from(""));
+    let expected = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        datacontenttype: "application/json",
+        data: Data::from_serializable(json!({"a": "<b>"})).unwrap(),
+        extensions: extensions,
+    )
+    .unwrap();
+
+    assert_eq!(from_str(xml).unwrap(), expected);
+}
+
+#[test]
+fn large_integer_extension_is_rejected() {
+    let mut extensions = HashMap::new();
+    extensions.insert("big".to_owned(), ExtensionValue::Integer(i64::MAX));
+    let event = cloudevent_v1_0!(
+        event_type: "t",
+        source: "/s",
+        event_id: "1",
+        extensions: extensions,
+    )
+    .unwrap();
+
+    assert!(matches!(
+        to_string(&event),
+        Err(Error::InvalidExtensionValue(ref name)) if name == "big"
+    ));
+}
+
+#[test]
+fn extension_name_which_is_not_an_xml_name_is_rejected() {
+    for name in &["2fa", "trace id", "com:example"] {
+        let event = CloudEventV1_0Builder::default()
+            .event_type("t")
+            .source("/s")
+            .event_id("1")
+            .extension(*name, "value")
+            .build()
+            .unwrap();
+
+        assert!(matches!(
+            to_string(&event),
+            Err(Error::InvalidExtensionName(ref n)) if n == name
+        ));
+    }
+}
+
+#[test]
+fn duplicate_data_elements_are_rejected() {
+    let ns = r#"xmlns:ce="http://cloudevents.io/xmlformat/V1""#;
+    let attributes = "<ce:type>t</ce:type><ce:source>/s</ce:source><ce:id>1</ce:id>";
+
+    for data in &[
+        "<ce:dataText>a</ce:dataText><ce:dataText>b</ce:dataText>",
+        "<ce:dataText>a</ce:dataText><ce:dataBinary>Yg==</ce:dataBinary>",
+        "<ce:dataXml><a/></ce:dataXml><ce:dataText>b</ce:dataText>",
+    ] {
+        let xml = format!(
+            r#"<ce:event {} specversion="1.0">{}{}</ce:event>"#,
+            ns, attributes, data
+        );
+        assert!(matches!(from_str(&xml), Err(Error::InvalidXmlDocument(_))));
+    }
+}
+
+#[test]
+fn invalid_documents_are_rejected() {
+    let ns = r#"xmlns:ce="http://cloudevents.io/xmlformat/V1""#;
+
+    assert!(matches!(
+        from_str(&format!(
+            r#"<ce:event {} specversion="1.0"><ce:id>1</ce:event>"#,
+            ns
+        )),
+        Err(Error::Xml(_))
+    ));
+    assert!(matches!(
+        from_str(r#"<event specversion="1.0"/>"#),
+        Err(Error::InvalidXmlDocument(_))
+    ));
+    assert!(matches!(
+        from_str(&format!(r#"<ce:event {}/>"#, ns)),
+        Err(Error::MissingRequiredAttribute("specversion"))
+    ));
+    assert!(matches!(
+        from_str(&format!(
+            r#"<ce:event {} specversion="1.0"><ce:id>1<b/></ce:id></ce:event>"#,
+            ns
+        )),
+        Err(Error::InvalidXmlDocument(_))
+    ));
+    assert!(matches!(
+        from_str(&format!(
+            r#"<ce:event {} xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" specversion="1.0"><ce:ext xsi:type="xs:double">1.5</ce:ext></ce:event>"#,
+            ns
+        )),
+        Err(Error::InvalidAttributeValue(ref name)) if name == "ext"
+    ));
+}